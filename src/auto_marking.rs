@@ -0,0 +1,259 @@
+use crate::commit_log::CommitInfo;
+use crate::diff_settings::DiffSettings;
+use crate::repository::Repository;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+
+pub type AutoMarkingRules = Vec<AutoMarkingRule>;
+
+const RULES_KEY: &str = "rules";
+const AUTHOR_KEY: &str = "author";
+const MESSAGE_PATTERN_KEY: &str = "messagePattern";
+const EXCLUDED_MESSAGE_PATTERN_KEY: &str = "excludedMessagePattern";
+const PATHS_KEY: &str = "paths";
+const MIN_CHANGED_LINES_KEY: &str = "minChangedLines";
+const RULES_EXAMPLE: &str = r#"# Commits matching all conditions of any rule are marked for report automatically.
+# Example:
+# [[rules]]
+# author = "John Smith"
+# excludedMessagePattern = "^(Merge|Bump version|fix typo)"
+# paths = ["src"]
+# minChangedLines = 5
+"#;
+
+// A commit matches a rule when it fulfills all conditions set in the rule.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AutoMarkingRule
+{
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub messagePattern: Option<String>,
+    #[serde(default)]
+    pub excludedMessagePattern: Option<String>,
+    // at least one changed file has to be inside one of these paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub minChangedLines: Option<usize>
+}
+
+#[derive(Serialize)]
+struct AutoMarkingRulesDocument
+{
+    rules: AutoMarkingRules
+}
+
+pub fn formatAutoMarkingRules(rules: &[AutoMarkingRule]) -> String
+{
+    if rules.is_empty() {
+        return RULES_EXAMPLE.into();
+    }
+    toml::to_string(&AutoMarkingRulesDocument{rules: rules.to_vec()}).unwrap()
+}
+
+// Rules are checked field by field, so that errors name the rule and the field, which need fixing.
+pub fn parseAutoMarkingRules(text: &str) -> Result<AutoMarkingRules, String>
+{
+    let document = text.parse::<toml::Table>().map_err(|e| format!("Rules are not valid TOML: {}", e))?;
+    let mut rules = vec![];
+    for (key, value) in document {
+        if key != RULES_KEY {
+            return Err(format!("Unknown setting \"{}\", each rule starts with [[{}]].", key, RULES_KEY));
+        }
+        let items = match value {
+            toml::Value::Array(items) => items,
+            _ => return Err(format!("Each rule has to start with [[{}]].", RULES_KEY))
+        };
+        for (index, item) in items.into_iter().enumerate() {
+            rules.push(parseRule(index + 1, item)?);
+        }
+    }
+    Ok(rules)
+}
+
+fn parseRule(number: usize, value: toml::Value) -> Result<AutoMarkingRule, String>
+{
+    let table = match value {
+        toml::Value::Table(table) => table,
+        _ => return Err(format!("Rule {} has to start with [[{}]].", number, RULES_KEY))
+    };
+    if table.is_empty() {
+        return Err(format!("Rule {} has no conditions, so it would mark every commit.", number));
+    }
+
+    let mut rule = AutoMarkingRule::default();
+    for (key, value) in table {
+        let makeError = |problem: &str| format!("Rule {}, field \"{}\": {}.", number, key, problem);
+        match key.as_str() {
+            AUTHOR_KEY => rule.author = Some(parseText(value).ok_or_else(|| makeError("expected a text"))?),
+            MESSAGE_PATTERN_KEY | EXCLUDED_MESSAGE_PATTERN_KEY => {
+                let pattern = parseText(value).ok_or_else(|| makeError("expected a regular expression in quotes"))?;
+                if let Err(e) = Regex::new(&pattern) {
+                    return Err(makeError(&format!("invalid regular expression: {}", e)));
+                }
+                match key.as_str() {
+                    MESSAGE_PATTERN_KEY => rule.messagePattern = Some(pattern),
+                    _ => rule.excludedMessagePattern = Some(pattern)
+                }
+            },
+            PATHS_KEY => rule.paths = parsePaths(value).ok_or_else(|| makeError("expected a list of paths in quotes"))?,
+            MIN_CHANGED_LINES_KEY => rule.minChangedLines = Some(match value {
+                toml::Value::Integer(lines) => lines.try_into().map_err(|_| makeError("expected at least 0"))?,
+                _ => return Err(makeError("expected a whole number"))
+            }),
+            _ => return Err(makeError("unknown field"))
+        }
+    }
+    Ok(rule)
+}
+
+fn parseText(value: toml::Value) -> Option<String>
+{
+    match value {
+        toml::Value::String(text) => Some(text),
+        _ => None
+    }
+}
+
+fn parsePaths(value: toml::Value) -> Option<Vec<String>>
+{
+    match value {
+        toml::Value::Array(items) => items.into_iter().map(parseText).collect(),
+        _ => None
+    }
+}
+
+#[derive(Default)]
+pub struct AutoMarker
+{
+    rules: Vec<CompiledRule>
+}
+
+impl AutoMarker
+{
+    pub fn new(rules: &[AutoMarkingRule]) -> Result<Self, String>
+    {
+        let rules = rules.iter().map(CompiledRule::new).collect::<Result<_, _>>()?;
+        Ok(Self{rules})
+    }
+
+    // Changed lines are counted with the same diff settings as in the commit log.
+    pub fn matches(
+        &self,
+        commitInfo: &CommitInfo,
+        commit: &git2::Commit,
+        repo: &Repository,
+        diffSettings: &DiffSettings)
+        -> bool
+    {
+        let mut commitChangesOpt = None;
+        self.rules.iter().any(|rule| rule.matches(commitInfo, commit, repo, diffSettings, &mut commitChangesOpt))
+    }
+}
+
+struct CompiledRule
+{
+    author: Option<String>,
+    messageRegex: Option<Regex>,
+    excludedMessageRegex: Option<Regex>,
+    paths: Vec<String>,
+    minChangedLines: Option<usize>
+}
+
+impl CompiledRule
+{
+    fn new(rule: &AutoMarkingRule) -> Result<Self, String>
+    {
+        Ok(Self{
+            author: rule.author.clone(),
+            messageRegex: compileRegex(&rule.messagePattern)?,
+            excludedMessageRegex: compileRegex(&rule.excludedMessagePattern)?,
+            paths: rule.paths.clone(),
+            minChangedLines: rule.minChangedLines
+        })
+    }
+
+    fn matches(
+        &self,
+        commitInfo: &CommitInfo,
+        commit: &git2::Commit,
+        repo: &Repository,
+        diffSettings: &DiffSettings,
+        commitChangesOpt: &mut Option<CommitChanges>)
+        -> bool
+    {
+        // co-authors of pair-programmed commits match too
+        if let Some(author) = &self.author {
+            if !commitInfo.isAuthoredBy(author) {
+                return false;
+            }
+        }
+
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        if let Some(regex) = &self.messageRegex {
+            if !regex.is_match(&message) {
+                return false;
+            }
+        }
+        if let Some(regex) = &self.excludedMessageRegex {
+            if regex.is_match(&message) {
+                return false;
+            }
+        }
+
+        if self.paths.is_empty() && self.minChangedLines.is_none() {
+            return true;
+        }
+
+        // calculating a diff is expensive, so do it only once per commit and only when a rule needs it
+        let commitChanges = commitChangesOpt.get_or_insert_with(|| CommitChanges::new(commit, repo, diffSettings));
+        if !self.paths.is_empty() && !commitChanges.touchesAnyOf(&self.paths) {
+            return false;
+        }
+        match self.minChangedLines {
+            Some(minChangedLines) => commitChanges.changedLines >= minChangedLines,
+            None => true
+        }
+    }
+}
+
+struct CommitChanges
+{
+    changedFiles: Vec<String>,
+    changedLines: usize
+}
+
+impl CommitChanges
+{
+    fn new(commit: &git2::Commit, repo: &Repository, diffSettings: &DiffSettings) -> Self
+    {
+        let diff = repo.makeDiffOfCommitAndParent(commit, diffSettings);
+        let stats = diff.stats().unwrap();
+        let changedFiles = diff.deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(|path| path.to_string_lossy().into())
+            .collect();
+        Self{changedFiles, changedLines: stats.insertions() + stats.deletions()}
+    }
+
+    fn touchesAnyOf(&self, paths: &[String]) -> bool
+    {
+        self.changedFiles.iter().any(|file| paths.iter().any(|path| Path::new(file).starts_with(path)))
+    }
+}
+
+fn compileRegex(patternOpt: &Option<String>) -> Result<Option<Regex>, String>
+{
+    match patternOpt {
+        Some(pattern) => match Regex::new(pattern) {
+            Ok(regex) => Ok(Some(regex)),
+            Err(e) => Err(format!("Invalid regular expression \"{}\": {}", pattern, e))
+        },
+        None => Ok(None)
+    }
+}
@@ -2,12 +2,15 @@ use gtk::glib;
 
 const DEFAULT_CONTEXT : Option<&glib::MainContext> = None;
 
+// The whole event bus is built on glib channels, so keep them until it is ported to async ones.
+#[allow(deprecated)]
 #[must_use]
 pub fn makeChannel<MessageType>() -> (glib::Sender<MessageType>, glib::Receiver<MessageType>)
 {
     glib::MainContext::channel(glib::Priority::default())
 }
 
+#[allow(deprecated)]
 pub fn attach<MessageType, HandlerType>(receiver: glib::Receiver<MessageType>, handler: HandlerType)
     where HandlerType: FnMut(MessageType) -> glib::ControlFlow + 'static
 {
@@ -0,0 +1,69 @@
+use crate::config_path::ConfigPath;
+use crate::config_store::ConfigStore;
+use crate::report_verification::verifyReportFolder;
+use crate::repository::Repository;
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const VERIFY_COMMAND: &str = "verify";
+const USAGE: &str = "\
+Usage:
+  rusty-tax-break
+      Starts the GUI.
+  rusty-tax-break verify <report folder> [<repository folder>]
+      Checks archives in the report folder against their manifests and the repository.
+      The folder can be also the output folder, then all its year and month folders are checked.
+      The repository from the config is used when none is given.";
+
+
+// Returns None when no command is given, which means the GUI should be started.
+// Arguments are taken as they are, since folder paths do not have to be valid UTF-8.
+pub fn runCommand(args: &[OsString], configPath: &ConfigPath) -> Option<ExitCode>
+{
+    match args {
+        [] => None,
+        [command, reportFolder] if command == VERIFY_COMMAND => {
+            let repositoryPath = ConfigStore::new(configPath).getConfig().repository.clone();
+            Some(verify(Path::new(reportFolder), repositoryPath))
+        },
+        [command, reportFolder, repositoryPath] if command == VERIFY_COMMAND =>
+            Some(verify(Path::new(reportFolder), Some(PathBuf::from(repositoryPath)))),
+        _ => {
+            eprintln!("{}", USAGE);
+            Some(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn verify(reportFolder: &Path, repositoryPath: Option<PathBuf>) -> ExitCode
+{
+    let repositoryPath = match repositoryPath {
+        Some(path) => path,
+        None => {
+            eprintln!("No repository given and none is set in the config.");
+            return ExitCode::FAILURE;
+        }
+    };
+    let repo = match git2::Repository::open(&repositoryPath) {
+        Ok(gitRepo) => Repository::new(gitRepo, repositoryPath),
+        Err(e) => {
+            eprintln!("Failed to open repository at {:?}, cause: {}", repositoryPath, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = verifyReportFolder(reportFolder, &repo);
+    for problem in &result.problems {
+        println!("{}", problem);
+    }
+    for warning in &result.warnings {
+        println!("Warning: {}", warning);
+    }
+    println!("{}", result.formatTotals());
+    match result.problems.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+
+pub type CommitAnnotations = BTreeMap<String, CommitAnnotation>;
+pub type Hours = f64;
+pub type Percentage = u8;
+
+const MAX_PERCENTAGE: Percentage = 100;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CommitAnnotation
+{
+    #[serde(default)]
+    pub hours: Option<Hours>,
+    // share of the commit, which is creative work, for reports which need it instead of hours
+    #[serde(default)]
+    pub creativeWorkPercentage: Option<Percentage>,
+    #[serde(default)]
+    pub description: String
+}
+
+impl CommitAnnotation
+{
+    pub fn isEmpty(&self) -> bool
+    {
+        self.hours.is_none() && self.creativeWorkPercentage.is_none() && self.description.is_empty()
+    }
+
+    pub fn formatHours(&self) -> String
+    {
+        match self.hours {
+            Some(hours) => hours.to_string(),
+            None => String::new()
+        }
+    }
+
+    pub fn formatCreativeWorkPercentage(&self) -> String
+    {
+        match self.creativeWorkPercentage {
+            Some(percentage) => format!("{}%", percentage),
+            None => String::new()
+        }
+    }
+
+    // Following lines of the description are indented, so that they never make an empty line, which ends entries
+    // in the report summary.
+    pub fn formatForReport(&self) -> String
+    {
+        format!(
+            "Hours: {}\nCreative work: {}\nDescription: {}\n",
+            self.formatHours(),
+            self.formatCreativeWorkPercentage(),
+            self.description.replace('\n', "\n  "))
+    }
+}
+
+pub fn parseHours(text: &str) -> Result<Option<Hours>, String>
+{
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.replace(',', ".").parse::<Hours>() {
+        Ok(hours) if hours.is_finite() && hours >= 0.0 => Ok(Some(hours)),
+        Ok(_) => Err(format!("Hours must be a non-negative number, got: {}", text)),
+        Err(e) => Err(format!("Failed to parse hours from \"{}\": {}", text, e))
+    }
+}
+
+pub fn parseCreativeWorkPercentage(text: &str) -> Result<Option<Percentage>, String>
+{
+    let text = text.trim();
+    let text = text.strip_suffix('%').unwrap_or(text).trim_end();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.parse::<Percentage>() {
+        Ok(percentage) if percentage <= MAX_PERCENTAGE => Ok(Some(percentage)),
+        Ok(_) => Err(format!("Creative work percentage must be at most {}, got: {}", MAX_PERCENTAGE, text)),
+        Err(e) => Err(format!("Failed to parse creative work percentage from \"{}\": {}", text, e))
+    }
+}
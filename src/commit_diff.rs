@@ -1,18 +1,61 @@
+use crate::commit_trailers::parseTrailers;
+use crate::date_format::DateFormat;
 use crate::date_time::makeDateTime;
 use crate::diff_formatter::DiffFormatter;
+use crate::repository::Repository;
+use crate::text_normalization::{normalizeLine, TextNormalizer};
 
-use time::format_description::well_known::Rfc2822;
+use std::fmt::Write as _;
+use time::OffsetDateTime;
 
 
-pub fn makeCommitSummary(commit: &git2::Commit) -> String
+pub fn makeCommitSummary(commit: &git2::Commit, repo: &Repository, dateFormat: DateFormat) -> String
 {
-    format!(
-        "Commit: {}\nAuthor: {} <{}>\nDate:   {}\n\n{}\n",
-        commit.id(),
-        commit.author().name().unwrap(),
-        commit.author().email().unwrap(),
-        formatDateTime(&commit.time()),
-        tabulateCommitMessage(&getMessage(commit)))
+    makeSummary(commit, repo, &repo.findRefNames(commit.id()), |dateTime| dateFormat.formatForDiff(dateTime))
+}
+
+// Refs are left out of reports, as they move and regenerating a report would give a different file.
+pub fn makeReportCommitSummary(commit: &git2::Commit, repo: &Repository, dateFormat: DateFormat) -> String
+{
+    makeSummary(commit, repo, &[], |dateTime| dateFormat.formatForReport(dateTime))
+}
+
+fn makeSummary(
+    commit: &git2::Commit,
+    repo: &Repository,
+    refNames: &[String],
+    formatDate: impl Fn(&OffsetDateTime) -> String)
+    -> String
+{
+    let message = getMessage(commit);
+    let mut summary = String::new();
+    writeField(&mut summary, "Commit", &commit.id().to_string());
+    writeField(&mut summary, "Parents", &formatParents(commit));
+    if !refNames.is_empty() {
+        writeField(&mut summary, "Refs", &refNames.join(", "));
+    }
+    writeField(&mut summary, "Author", &formatSignature(&commit.author()));
+    writeField(&mut summary, "AuthorDate", &formatDate(&makeDateTime(&commit.author().when())));
+    writeField(&mut summary, "Committer", &formatSignature(&commit.committer()));
+    // keeps the commit time, which it held before the author date was added
+    writeField(&mut summary, "Date", &formatDate(&makeDateTime(&commit.time())));
+    let signature = match repo.findSignatureKind(commit.id()) {
+        Some(kind) => format!("{} (not verified)", kind),
+        None => "none".into()
+    };
+    writeField(&mut summary, "Signature", &signature);
+    summary.push('\n');
+    summary.push_str(&tabulateCommitMessage(&message));
+
+    let trailers = parseTrailers(&message);
+    if !trailers.is_empty() {
+        summary.push_str("\nTrailers:\n");
+        for trailer in trailers {
+            writeln!(summary, "    {}: {}", trailer.key, trailer.value).unwrap();
+        }
+    }
+    summary.push('\n');
+    summary
 }
 
 pub fn makeFormattedDiff(diff: &git2::Diff) -> String
@@ -22,6 +65,52 @@ pub fn makeFormattedDiff(diff: &git2::Diff) -> String
     diffFormatter.takeText()
 }
 
+// Used for reports, where files can be converted from legacy encodings and get normalized line endings.
+pub fn makeNormalizedFormattedDiff(diff: &git2::Diff, normalizer: &TextNormalizer) -> String
+{
+    let mut diffFormatter = DiffFormatter::new();
+    let mut currentFile = None;
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        // headers keep paths and hunk ranges, which are not in the encoding of the file
+        if !['+', '-', ' '].contains(&line.origin()) {
+            return diffFormatter.format(&line);
+        }
+        let path = delta.new_file().path().map(|path| path.to_path_buf());
+        let format = match &currentFile {
+            Some((currentPath, format)) if *currentPath == path => *format,
+            _ => {
+                let format = normalizer.findDiffFileTextFormat(&delta);
+                currentFile = Some((path, format));
+                format
+            }
+        };
+        diffFormatter.formatWithContent(&line, &normalizeLine(line.content(), &format))
+    }).unwrap();
+    diffFormatter.takeText()
+}
+
+// Header fields are aligned like in "git log --format=fuller".
+fn writeField(summary: &mut String, name: &str, value: &str)
+{
+    writeln!(summary, "{:<12}{}", format!("{}:", name), value).unwrap();
+}
+
+fn formatParents(commit: &git2::Commit) -> String
+{
+    match commit.parent_count() {
+        0 => "none".into(),
+        _ => commit.parent_ids().map(|id| id.to_string()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+fn formatSignature(signature: &git2::Signature) -> String
+{
+    format!(
+        "{} <{}>",
+        String::from_utf8_lossy(signature.name_bytes()),
+        String::from_utf8_lossy(signature.email_bytes()))
+}
+
 fn getMessage(commit: &git2::Commit) -> String
 {
     match commit.message() {
@@ -30,11 +119,6 @@ fn getMessage(commit: &git2::Commit) -> String
     }
 }
 
-fn formatDateTime(inputTime: &git2::Time) -> String
-{
-    makeDateTime(inputTime).format(&Rfc2822).unwrap()
-}
-
 fn tabulateCommitMessage(message: &str) -> String
 {
     let mut result = String::new();
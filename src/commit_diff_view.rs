@@ -1,20 +1,51 @@
 use crate::commit_diff::{makeCommitSummary, makeFormattedDiff};
-use crate::diff_colorizer::DiffColorizer;
+use crate::config_store::Config;
+use crate::date_format::DateFormat;
+use crate::diff_colorizer::{DiffColorizer, FILE_HEADER_PREFIX, makeCollapsedSectionTag};
+use crate::diff_colors::{DiffColors, DiffColorSettings};
+use crate::diff_files_view::{DiffFilesView, FileIndex, getSelectedFile};
+use crate::diff_search::{DiffSearch, DiffSearchQuery};
+use crate::diff_settings::DiffSettings;
 use crate::event::Event;
 use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
+use crate::line_number::LineNumber;
 use crate::repository::Repository;
+use crate::side_by_side_diff::makeSideBySideDiff;
+use crate::side_by_side_diff_view::SideBySideDiffView;
 use crate::source::Source;
 use crate::text_view::TextView;
 
+use gtk::prelude::GtkSettingsExt as _;
+use gtk::prelude::StackExt as _;
+use gtk::prelude::ToggleButtonExt as _;
+use std::collections::HashSet;
 use std::rc::Rc;
 
+const UNIFIED_PAGE: &str = "unified";
+const SIDE_BY_SIDE_PAGE: &str = "sideBySide";
+const CONTINUE_PRINTING: bool = true;
+
 
 pub struct CommitDiffView
 {
     textView: TextView,
     diffColorizer: DiffColorizer,
-    repository: Option<Rc<Repository>>
+    collapsedSectionTag: gtk::TextTag,
+    sideBySideView: SideBySideDiffView,
+    filesView: DiffFilesView,
+    search: DiffSearch,
+    stack: gtk::Stack,
+    isSideBySide: bool,
+    commitId: Option<git2::Oid>,
+    // indexed like files in the files view, files which print no patch have no section
+    fileSections: Vec<Option<FileSection>>,
+    collapsedFiles: HashSet<FileIndex>,
+    repository: Option<Rc<Repository>>,
+    dateFormat: DateFormat,
+    colorSettings: DiffColorSettings,
+    diffSettings: DiffSettings,
+    sender: Sender
 }
 
 impl EventHandler for CommitDiffView
@@ -22,10 +53,20 @@ impl EventHandler for CommitDiffView
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::CommitSelected(id)      => self.onCommitSelected(id),
-            Event::CommitUnselected        => self.onCommitUnselected(),
-            Event::RepositoryChanged(repo) => self.onRepositoryChanged(repo),
-            Event::ZoomRequested(_)        => self.onZoomRequested(source, event),
+            Event::CommitSelected(id)                  => self.onCommitSelected(id),
+            Event::CommitUnselected                    => self.onCommitUnselected(),
+            Event::DateFormatChanged(format)           => self.onDateFormatChanged(*format),
+            Event::DiffColorsChanged(settings)         => self.onDiffColorsChanged(settings),
+            Event::DiffFileSectionToggled(treePath)    => self.onDiffFileSectionToggled(treePath),
+            Event::DiffSearchChanged(query)            => self.onDiffSearchChanged(query),
+            Event::DiffSearchNextRequested             => self.onDiffSearchNextRequested(),
+            Event::DiffSearchPreviousRequested         => self.onDiffSearchPreviousRequested(),
+            Event::DiffSettingsChanged(settings)       => self.onDiffSettingsChanged(settings),
+            Event::RepositoryChanged(repo)             => self.onRepositoryChanged(repo),
+            Event::SelectionChanged(selection)         => self.onDiffFileSelected(selection),
+            Event::SideBySideDiffToggled(isSideBySide) => self.onSideBySideDiffToggled(*isSideBySide),
+            Event::ThemeChanged                        => self.updateColors(),
+            Event::ZoomRequested(_)                    => self.onZoomRequested(source, event),
             _ => onUnknown(source, event)
         }
     }
@@ -33,16 +74,46 @@ impl EventHandler for CommitDiffView
 
 impl CommitDiffView
 {
-    pub fn new(repository: Option<Rc<Repository>>, guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    pub fn new(
+        config: &Config,
+        repository: Option<Rc<Repository>>,
+        guiElementProvider: &GuiElementProvider,
+        sender: Sender)
+        -> Self
     {
-        let textView = TextView::new(guiElementProvider, "commitDiffView", sender, Source::CommitDiffViewWidget);
-        let diffColorizer = DiffColorizer::new();
+        let textView = TextView::new(
+            guiElementProvider, "commitDiffView", config.diffFontSize, sender.clone(), Source::CommitDiffViewWidget);
+        let colors = DiffColors::new(&config.diffColors, textView.isThemeDark());
+        let diffColorizer = DiffColorizer::new(&colors);
         diffColorizer.setupTextView(&textView);
-        Self{
+        let collapsedSectionTag = makeCollapsedSectionTag();
+        textView.registerTags(&[&collapsedSectionTag]);
+        let sideBySideView = SideBySideDiffView::new(guiElementProvider, &colors, config.diffFontSize, sender.clone());
+        let filesView = DiffFilesView::new(guiElementProvider, sender.clone());
+        let search = DiffSearch::new(guiElementProvider, sender.clone());
+        let stack = guiElementProvider.get::<gtk::Stack>("commitDiffStack");
+        setupSideBySideDiffToggleButton(config.isDiffSideBySide, guiElementProvider, sender.clone());
+        setupThemeChangeDetection(sender.clone());
+        let newSelf = Self{
             textView,
             diffColorizer,
-            repository
-        }
+            collapsedSectionTag,
+            sideBySideView,
+            filesView,
+            search,
+            stack,
+            isSideBySide: config.isDiffSideBySide,
+            commitId: None,
+            fileSections: vec![],
+            collapsedFiles: HashSet::new(),
+            repository,
+            dateFormat: config.dateFormat,
+            colorSettings: config.diffColors.clone(),
+            diffSettings: config.diffSettings.clone(),
+            sender
+        };
+        newSelf.showCurrentPage();
+        newSelf
     }
 
 
@@ -50,20 +121,77 @@ impl CommitDiffView
 
     fn onCommitSelected(&mut self, commitId: &git2::Oid)
     {
-        match &self.repository {
-            Some(repository) => {
-                let commit = repository.findCommit(*commitId).unwrap();
-                let commitTreesDiff = repository.makeDiffOfCommitAndParent(&commit);
-                let textDiff = makeCommitSummary(&commit) + &makeFormattedDiff(&commitTreesDiff);
-                self.diffColorizer.colorize(&self.textView, &textDiff);
-            },
-            None => panic!("Repository not set yet")
-        }
+        self.commitId = Some(*commitId);
+        self.collapsedFiles.clear();
+        self.showCommit();
     }
 
-    fn onCommitUnselected(&self)
+    fn onCommitUnselected(&mut self)
     {
+        self.commitId = None;
         self.textView.clear();
+        self.sideBySideView.clear();
+        self.filesView.clear();
+        self.fileSections.clear();
+        self.searchDiff();
+    }
+
+    fn onDateFormatChanged(&mut self, format: DateFormat)
+    {
+        self.dateFormat = format;
+        self.showCommit();
+    }
+
+    fn onDiffColorsChanged(&mut self, settings: &DiffColorSettings)
+    {
+        self.colorSettings = settings.clone();
+        self.updateColors();
+    }
+
+    fn onDiffFileSelected(&self, selection: &gtk::TreeSelection)
+    {
+        let section = match getSelectedFile(selection).and_then(|fileIndex| self.findFileSection(fileIndex)) {
+            Some(section) => section,
+            None => return
+        };
+        match self.isSideBySide {
+            true => self.sideBySideView.scrollToLine(section.headerLine),
+            false => self.textView.scrollToLine(section.headerLine)
+        }
+    }
+
+    fn onDiffFileSectionToggled(&mut self, treePath: &gtk::TreePath)
+    {
+        let (fileIndex, isShown) = self.filesView.toggleShown(treePath);
+        match isShown {
+            true => self.collapsedFiles.remove(&fileIndex),
+            false => self.collapsedFiles.insert(fileIndex)
+        };
+        self.setSectionShown(fileIndex, isShown);
+    }
+
+    fn onDiffSearchChanged(&mut self, query: &DiffSearchQuery)
+    {
+        self.search.setQuery(query);
+        self.searchDiff();
+    }
+
+    fn onDiffSearchNextRequested(&mut self)
+    {
+        let textViews = getShownTextViews(self.isSideBySide, &self.textView, &self.sideBySideView);
+        self.search.goToNextMatch(&textViews);
+    }
+
+    fn onDiffSearchPreviousRequested(&mut self)
+    {
+        let textViews = getShownTextViews(self.isSideBySide, &self.textView, &self.sideBySideView);
+        self.search.goToPreviousMatch(&textViews);
+    }
+
+    fn onDiffSettingsChanged(&mut self, settings: &DiffSettings)
+    {
+        self.diffSettings = settings.clone();
+        self.showCommit();
     }
 
     fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
@@ -71,8 +199,183 @@ impl CommitDiffView
         self.repository = Some(Rc::clone(repo));
     }
 
+    fn onSideBySideDiffToggled(&mut self, isSideBySide: bool)
+    {
+        self.isSideBySide = isSideBySide;
+        self.showCurrentPage();
+        self.showCommit();
+    }
+
     fn onZoomRequested(&mut self, source: Source, event: &Event)
     {
         self.textView.handle(source, event);
+        self.sideBySideView.zoom(source, event);
+        // all views share the same font size
+        self.sender.send((Source::CommitDiffView, Event::DiffFontSizeChanged(self.textView.getFontSize()))).unwrap();
+    }
+
+    fn updateColors(&self)
+    {
+        let colors = DiffColors::new(&self.colorSettings, self.textView.isThemeDark());
+        self.diffColorizer.setColors(&colors);
+        self.sideBySideView.setColors(&colors);
+    }
+
+    fn showCurrentPage(&self)
+    {
+        match self.isSideBySide {
+            true => self.stack.set_visible_child_name(SIDE_BY_SIDE_PAGE),
+            false => self.stack.set_visible_child_name(UNIFIED_PAGE)
+        }
+    }
+
+    // The header line of a file stays visible, so that the file can still be found in the diff.
+    fn setSectionShown(&self, fileIndex: FileIndex, isShown: bool)
+    {
+        let section = match self.findFileSection(fileIndex) {
+            Some(section) => section,
+            None => return
+        };
+        let startLine = section.headerLine + 1;
+        match (self.isSideBySide, isShown) {
+            (true, _) => self.sideBySideView.setSectionShown(startLine, section.endLine, isShown),
+            (false, true) => self.textView.removeTag(&self.collapsedSectionTag, startLine, section.endLine),
+            (false, false) => self.textView.applyTag(&self.collapsedSectionTag, startLine, section.endLine)
+        }
+    }
+
+    fn showCommit(&mut self)
+    {
+        let commitId = match self.commitId {
+            Some(commitId) => commitId,
+            None => return
+        };
+        let repository = match &self.repository {
+            Some(repository) => Rc::clone(repository),
+            None => panic!("Repository not set yet")
+        };
+
+        let commit = repository.findCommit(commitId).unwrap();
+        let commitTreesDiff = repository.makeDiffOfCommitAndParent(&commit, &self.diffSettings);
+        let commitSummary = makeCommitSummary(&commit, &repository, self.dateFormat);
+        let (fileHeaderLines, lineCount) = if self.isSideBySide {
+            let sideBySideDiff = makeSideBySideDiff(&commitSummary, &commitTreesDiff);
+            self.sideBySideView.show(&sideBySideDiff);
+            (sideBySideDiff.oldSide.findFileHeaderLines(), sideBySideDiff.oldSide.lineKinds.len())
+        } else {
+            let textDiff = commitSummary + &makeFormattedDiff(&commitTreesDiff);
+            self.diffColorizer.colorize(&self.textView, &textDiff);
+            (findFileHeaderLines(&textDiff), textDiff.lines().count())
+        };
+        let printedFiles = findPrintedFiles(&commitTreesDiff);
+        let fileCount = commitTreesDiff.deltas().len();
+        self.fileSections = makeFileSections(&fileHeaderLines, &printedFiles, fileCount, lineCount);
+        self.filesView.show(&commitTreesDiff, &self.collapsedFiles);
+        for fileIndex in &self.collapsedFiles {
+            self.setSectionShown(*fileIndex, false);
+        }
+        self.searchDiff();
+    }
+
+    fn findFileSection(&self, fileIndex: FileIndex) -> Option<&FileSection>
+    {
+        self.fileSections.get(fileIndex).and_then(Option::as_ref)
     }
+
+    fn searchDiff(&mut self)
+    {
+        let textViews = getShownTextViews(self.isSideBySide, &self.textView, &self.sideBySideView);
+        self.search.search(&textViews);
+    }
+}
+
+// Lines of a file in the diff, from its header up to the header of the next file.
+struct FileSection
+{
+    headerLine: LineNumber,
+    endLine: LineNumber
+}
+
+fn findFileHeaderLines(textDiff: &str) -> Vec<usize>
+{
+    textDiff.lines().enumerate()
+        .filter(|(_lineNumber, line)| line.starts_with(FILE_HEADER_PREFIX))
+        .map(|(lineNumber, _line)| lineNumber)
+        .collect()
+}
+
+// Returns indices of deltas, which print a file header, in the order of the headers. Deltas print none, when they
+// have no changes left, e.g. after ignoring whitespace.
+fn findPrintedFiles(diff: &git2::Diff) -> Vec<FileIndex>
+{
+    let deltas = diff.deltas().collect::<Vec<_>>();
+    let mut printedFiles: Vec<FileIndex> = vec![];
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        if line.origin() == 'F' {
+            let start = printedFiles.last().map_or(0, |fileIndex| fileIndex + 1);
+            if let Some(offset) = deltas[start..].iter().position(|candidate| isSameFile(candidate, &delta)) {
+                printedFiles.push(start + offset);
+            }
+        }
+        CONTINUE_PRINTING
+    }).unwrap();
+    printedFiles
+}
+
+fn isSameFile(first: &git2::DiffDelta, second: &git2::DiffDelta) -> bool
+{
+    first.status() == second.status()
+        && first.old_file().path() == second.old_file().path()
+        && first.new_file().path() == second.new_file().path()
+}
+
+// Header lines follow the order of printed files, so each one gets the section of its delta.
+fn makeFileSections(
+    fileHeaderLines: &[usize],
+    printedFiles: &[FileIndex],
+    fileCount: usize,
+    lineCount: usize)
+    -> Vec<Option<FileSection>>
+{
+    let mut fileSections = (0..fileCount).map(|_| None).collect::<Vec<_>>();
+    for (index, (headerLine, fileIndex)) in fileHeaderLines.iter().zip(printedFiles).enumerate() {
+        fileSections[*fileIndex] = Some(FileSection{
+            headerLine: (*headerLine).into(),
+            endLine: fileHeaderLines.get(index + 1).copied().unwrap_or(lineCount).into()});
+    }
+    fileSections
+}
+
+fn getShownTextViews<'a>(
+    isSideBySide: bool, textView: &'a TextView, sideBySideView: &'a SideBySideDiffView) -> Vec<&'a TextView>
+{
+    match isSideBySide {
+        true => sideBySideView.getTextViews().to_vec(),
+        false => vec![textView]
+    }
+}
+
+fn setupSideBySideDiffToggleButton(isSideBySide: bool, guiElementProvider: &GuiElementProvider, sender: Sender)
+{
+    let button = guiElementProvider.get::<gtk::ToggleButton>("sideBySideDiffToggleButton");
+    button.set_active(isSideBySide);
+    button.connect_toggled(move |button| {
+        sender.send((Source::SideBySideDiffToggleButton, Event::SideBySideDiffToggled(button.is_active()))).unwrap();
+    });
+}
+
+// The automatic diff colors follow the GTK theme, also when it is changed while the application runs.
+fn setupThemeChangeDetection(sender: Sender)
+{
+    let settings = match gtk::Settings::default() {
+        Some(settings) => settings,
+        None => return
+    };
+    let themeSender = sender.clone();
+    settings.connect_gtk_theme_name_notify(move |_settings| {
+        themeSender.send((Source::CommitDiffViewWidget, Event::ThemeChanged)).unwrap();
+    });
+    settings.connect_gtk_application_prefer_dark_theme_notify(move |_settings| {
+        sender.send((Source::CommitDiffViewWidget, Event::ThemeChanged)).unwrap();
+    });
 }
@@ -1,18 +1,38 @@
+use crate::auto_marking::{AutoMarker, AutoMarkingRules};
+use crate::commit_annotation::{CommitAnnotation, CommitAnnotations};
+use crate::commit_trailers::{CoAuthor, findCoAuthors};
+use crate::config_store::Config;
 use crate::date_time::makeDateTime;
-use crate::event::Event;
+use crate::diff_settings::DiffSettings;
+use crate::error_dialog::showErrorDialog;
+use crate::event::{Event, Year};
 use crate::event_handling::{EventHandler, onUnknown, Sender};
+use crate::gui_element_provider::GuiElementProvider;
 use crate::repository::Repository;
 use crate::source::Source;
 
+use std::collections::HashSet;
 use std::rc::Rc;
-use time::OffsetDateTime;
+use time::{Date, Month, OffsetDateTime};
 
+const CO_AUTHORS_SEPARATOR: &str = ", ";
 const INVALID_UTF8: &str = "<invalid UTF-8>";
 
 
 pub struct CommitLog
 {
     commits: Vec<CommitInfo>,
+    annotations: CommitAnnotations,
+    autoMarker: AutoMarker,
+    diffSettings: DiffSettings,
+    // rules are evaluated only for months shown in the log, as it is slow for long histories
+    evaluatedMonths: HashSet<(Year, Month)>,
+    // diff stats are calculated for shown months only too, for the same reason
+    diffStatsMonths: HashSet<(Year, Month)>,
+    shownMonth: (Year, Month),
+    areDiffStatsRequested: bool,
+    repo: Option<Rc<Repository>>,
+    parentWindow: gtk::ApplicationWindow,
     sender: Sender
 }
 
@@ -21,7 +41,12 @@ impl EventHandler for CommitLog
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::RepositoryChanged(repo) => self.onRepositoryChanged(repo),
+            Event::AutoMarkingRulesChanged(rules) => self.onAutoMarkingRulesChanged(rules),
+            Event::CommitDiffStatsRequested       => self.onCommitDiffStatsRequested(),
+            Event::DiffSettingsChanged(settings)  => self.onDiffSettingsChanged(settings),
+            Event::MonthFilterChanged(month)      => self.onShownMonthChanged((self.shownMonth.0, *month)),
+            Event::RepositoryChanged(repo)        => self.onRepositoryChanged(repo),
+            Event::YearFilterChanged(year)        => self.onShownMonthChanged((*year, self.shownMonth.1)),
             _ => onUnknown(source, event)
         }
     }
@@ -29,11 +54,35 @@ impl EventHandler for CommitLog
 
 impl CommitLog
 {
-    pub fn new(repoOpt: &Option<Rc<Repository>>, sender: Sender) -> Self
+    // The log starts with the month of the current date, like the month and year filters.
+    pub fn new(
+        config: &Config,
+        repoOpt: &Option<Rc<Repository>>,
+        currentDate: Date,
+        guiElementProvider: &GuiElementProvider,
+        sender: Sender)
+        -> Self
     {
-        let mut newSelf = Self{commits: vec![], sender};
+        let parentWindow = guiElementProvider.get::<gtk::ApplicationWindow>("mainWindow");
+        let autoMarker = AutoMarker::new(&config.autoMarkingRules).unwrap_or_else(|e| {
+            showAutoMarkingRulesError(&parentWindow, &e);
+            AutoMarker::default()
+        });
+        let mut newSelf = Self{
+            commits: vec![],
+            annotations: config.commitAnnotations.clone(),
+            autoMarker,
+            diffSettings: config.diffSettings.clone(),
+            evaluatedMonths: HashSet::new(),
+            diffStatsMonths: HashSet::new(),
+            shownMonth: (currentDate.year(), currentDate.month()),
+            areDiffStatsRequested: false,
+            repo: repoOpt.clone(),
+            parentWindow,
+            sender};
         if let Some(repo) = repoOpt {
             newSelf.loadCommits(repo);
+            newSelf.applyAutoMarkingRules(repo);
         }
         newSelf
     }
@@ -43,6 +92,11 @@ impl CommitLog
         &self.commits
     }
 
+    pub fn getMarkedCommits(&self) -> impl Iterator<Item = &CommitInfo>
+    {
+        self.commits.iter().filter(|commit| commit.markedForReport)
+    }
+
     pub fn getCommit(&self, row: usize) -> Option<&CommitInfo>
     {
         self.commits.get(row)
@@ -50,15 +104,100 @@ impl CommitLog
 
     pub fn setMarkedForReport(&mut self, row: usize, markedForReport: bool)
     {
-        self.commits.get_mut(row).unwrap().markedForReport = markedForReport;
+        let commit = self.commits.get_mut(row).unwrap();
+        commit.markedForReport = markedForReport;
+        commit.markedBy = MarkedBy::User;
+    }
+
+    pub fn setAnnotation(&mut self, row: usize, annotation: CommitAnnotation)
+    {
+        let commit = self.commits.get_mut(row).unwrap();
+        if annotation.isEmpty() {
+            self.annotations.remove(&commit.id.to_string());
+        } else {
+            self.annotations.insert(commit.id.to_string(), annotation.clone());
+        }
+        commit.annotation = annotation;
     }
 
     // private
 
+    fn onAutoMarkingRulesChanged(&mut self, rules: &AutoMarkingRules)
+    {
+        self.autoMarker = match AutoMarker::new(rules) {
+            Ok(autoMarker) => autoMarker,
+            Err(e) => return showAutoMarkingRulesError(&self.parentWindow, &e)
+        };
+        self.reapplyAutoMarkingRules();
+    }
+
+    // Changed lines depend on diff settings, e.g. on ignoring whitespace, so both the column and rules use them.
+    fn onDiffSettingsChanged(&mut self, settings: &DiffSettings)
+    {
+        self.diffSettings = settings.clone();
+        self.diffStatsMonths.clear();
+        for commitInfo in &mut self.commits {
+            commitInfo.diffStats = None;
+        }
+        if let Some(repo) = self.repo.clone() {
+            if self.areDiffStatsRequested {
+                self.loadDiffStats(&repo);
+            }
+        }
+        self.reapplyAutoMarkingRules();
+    }
+
+    fn reapplyAutoMarkingRules(&mut self)
+    {
+        self.evaluatedMonths.clear();
+        for commitInfo in &mut self.commits {
+            if commitInfo.markedBy == MarkedBy::Rule {
+                commitInfo.markedForReport = false;
+                commitInfo.markedBy = MarkedBy::Nobody;
+            }
+        }
+        if let Some(repo) = self.repo.clone() {
+            self.applyAutoMarkingRules(&repo);
+            self.sender.send((Source::CommitLog, Event::CommitLogChanged)).unwrap();
+        }
+    }
+
+    fn onCommitDiffStatsRequested(&mut self)
+    {
+        if self.areDiffStatsRequested {
+            return;
+        }
+        self.areDiffStatsRequested = true;
+
+        if let Some(repo) = self.repo.clone() {
+            self.loadDiffStats(&repo);
+            self.sender.send((Source::CommitLog, Event::CommitLogChanged)).unwrap();
+        }
+    }
+
+    fn onShownMonthChanged(&mut self, shownMonth: (Year, Month))
+    {
+        self.shownMonth = shownMonth;
+        if let Some(repo) = self.repo.clone() {
+            let areDiffStatsLoaded = self.areDiffStatsRequested && self.loadDiffStats(&repo);
+            let anyMarked = self.applyAutoMarkingRules(&repo);
+            if areDiffStatsLoaded || anyMarked {
+                self.sender.send((Source::CommitLog, Event::CommitLogChanged)).unwrap();
+            }
+        }
+    }
+
     fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
     {
+        self.repo = Some(Rc::clone(repo));
         self.commits.clear();
+        self.evaluatedMonths.clear();
+        self.diffStatsMonths.clear();
         self.loadCommits(repo);
+        self.applyAutoMarkingRules(repo);
+        if self.areDiffStatsRequested {
+            self.loadDiffStats(repo);
+        }
         self.sender.send((Source::CommitLog, Event::CommitLogChanged)).unwrap();
     }
 
@@ -69,15 +208,62 @@ impl CommitLog
         }
 
         repo.iterateCommits(|commit| {
-            let summary = getSummary(commit);
-            let signature = commit.author();
-            let date = makeDateTime(&commit.time());
-            let author = signature.name().unwrap_or(INVALID_UTF8).into();
-            let email = signature.email().unwrap_or(INVALID_UTF8).into();
-            let id = commit.id();
-            self.commits.push(CommitInfo {id, summary, date, author, email, markedForReport: false});
+            let annotation = self.annotations.get(&commit.id().to_string()).cloned().unwrap_or_default();
+            self.commits.push(CommitInfo::new(commit, annotation));
         });
     }
+
+    // Calculating diffs of all commits is slow, so it is done only when some column needs them and only for commits
+    // of the shown month, unless it was done already. Returns whether any stats were calculated.
+    fn loadDiffStats(&mut self, repo: &Repository) -> bool
+    {
+        if !self.diffStatsMonths.insert(self.shownMonth) {
+            return false;
+        }
+
+        let mut anyLoaded = false;
+        for commitInfo in &mut self.commits {
+            if commitInfo.getMonth() != self.shownMonth {
+                continue;
+            }
+            let commit = repo.findCommit(commitInfo.id).unwrap();
+            let diff = repo.makeDiffOfCommitAndParent(&commit, &self.diffSettings);
+            let stats = diff.stats().unwrap();
+            commitInfo.diffStats = Some(DiffStats{
+                changedLines: stats.insertions() + stats.deletions(),
+                changedFiles: stats.files_changed()});
+            anyLoaded = true;
+        }
+        anyLoaded
+    }
+
+    // Evaluates rules for commits of the shown month, unless it was done already. Commits marked or unmarked
+    // manually are left as they are. Returns whether any commit got marked.
+    fn applyAutoMarkingRules(&mut self, repo: &Repository) -> bool
+    {
+        if !self.evaluatedMonths.insert(self.shownMonth) {
+            return false;
+        }
+
+        let mut anyMarked = false;
+        for commitInfo in &mut self.commits {
+            if commitInfo.markedBy == MarkedBy::User || commitInfo.getMonth() != self.shownMonth {
+                continue;
+            }
+            let commit = repo.findCommit(commitInfo.id).unwrap();
+            if self.autoMarker.matches(commitInfo, &commit, repo, &self.diffSettings) {
+                commitInfo.markedForReport = true;
+                commitInfo.markedBy = MarkedBy::Rule;
+                anyMarked = true;
+            }
+        }
+        anyMarked
+    }
+}
+
+fn showAutoMarkingRulesError(parentWindow: &gtk::ApplicationWindow, error: &str)
+{
+    showErrorDialog(parentWindow, &format!("Auto marking rules are not used. {}", error));
 }
 
 fn getSummary(commit: &git2::Commit) -> String
@@ -100,9 +286,80 @@ fn getSummaryFromRaw(commit: &git2::Commit) -> String
 pub struct CommitInfo
 {
     pub id: git2::Oid,
+    pub shortId: String,
     pub summary: String,
     pub date: OffsetDateTime,
     pub author: String,
     pub email: String,
-    pub markedForReport: bool
+    pub coAuthors: Vec<CoAuthor>,
+    pub committer: String,
+    pub markedForReport: bool,
+    pub markedBy: MarkedBy,
+    pub annotation: CommitAnnotation,
+    pub diffStats: Option<DiffStats>
+}
+
+impl CommitInfo
+{
+    pub fn new(commit: &git2::Commit, annotation: CommitAnnotation) -> Self
+    {
+        let signature = commit.author();
+        Self{
+            id: commit.id(),
+            shortId: commit.as_object().short_id().unwrap().as_str().unwrap().into(),
+            summary: getSummary(commit),
+            date: makeDateTime(&commit.time()),
+            author: signature.name().unwrap_or(INVALID_UTF8).into(),
+            email: signature.email().unwrap_or(INVALID_UTF8).into(),
+            coAuthors: findCoAuthors(&String::from_utf8_lossy(commit.message_bytes())),
+            committer: commit.committer().name().unwrap_or(INVALID_UTF8).into(),
+            markedForReport: false,
+            markedBy: MarkedBy::Nobody,
+            annotation,
+            diffStats: None
+        }
+    }
+
+    // Pair-programmed commits belong to each of their authors.
+    pub fn isAuthoredBy(&self, name: &str) -> bool
+    {
+        self.author == name || self.coAuthors.iter().any(|coAuthor| coAuthor.name == name)
+    }
+
+    pub fn getMonth(&self) -> (Year, Month)
+    {
+        (self.date.year(), self.date.month())
+    }
+
+    pub fn formatCoAuthorNames(&self) -> String
+    {
+        self.coAuthors.iter()
+            .map(|coAuthor| coAuthor.name.as_str())
+            .collect::<Vec<_>>()
+            .join(CO_AUTHORS_SEPARATOR)
+    }
+
+    pub fn formatCoAuthors(&self) -> String
+    {
+        self.coAuthors.iter()
+            .map(|coAuthor| coAuthor.format())
+            .collect::<Vec<_>>()
+            .join(CO_AUTHORS_SEPARATOR)
+    }
+}
+
+// Rules mark only commits, which were neither marked nor unmarked by the user.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkedBy
+{
+    Nobody,
+    Rule,
+    User
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DiffStats
+{
+    pub changedLines: usize,
+    pub changedFiles: usize
 }
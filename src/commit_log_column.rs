@@ -5,7 +5,19 @@ pub enum CommitLogColumn
     Date,
     Author,
     Email,
-    OriginalRow
+    Hours,
+    CreativeWork,
+    Description,
+    MarkedBy,
+    ShortId,
+    Committer,
+    LinesChanged,
+    FilesChanged,
+    CoAuthors,
+    OriginalRow,
+    DateTimestamp,
+    HoursValue,
+    CreativeWorkValue
 }
 
 impl From<CommitLogColumn> for i32
@@ -0,0 +1,123 @@
+use crate::event::Event;
+use crate::event_handling::Sender;
+use crate::source::Source;
+
+use gtk::gdk;
+use gtk::glib;
+use gtk::prelude::CheckMenuItemExt as _;
+use gtk::prelude::GtkMenuExt as _;
+use gtk::prelude::MenuShellExt as _;
+use gtk::prelude::TreeViewColumnExt as _;
+use gtk::prelude::TreeViewExt as _;
+use gtk::prelude::WidgetExt as _;
+use serde::{Deserialize, Serialize};
+
+const RIGHT_MOUSE_BUTTON: u32 = 3;
+const UNSET_WIDTH: i32 = -1;
+
+
+pub type CommitLogColumnsSettings = Vec<CommitLogColumnSettings>;
+
+// Columns are identified by their titles, so that settings survive adding new columns.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CommitLogColumnSettings
+{
+    pub title: String,
+    pub isVisible: bool,
+    #[serde(default = "defaultWidth")]
+    pub width: i32
+}
+
+pub fn setupCommitLogColumns(widget: &gtk::TreeView, settings: &[CommitLogColumnSettings], sender: Sender)
+{
+    restoreColumns(widget, settings);
+    setupHeaderMenu(widget);
+    connectColumnChanges(widget, sender.clone());
+    sendColumnsChanged(widget, &sender);
+}
+
+fn restoreColumns(widget: &gtk::TreeView, settings: &[CommitLogColumnSettings])
+{
+    let mut previousColumnOpt = None;
+    for columnSettings in settings {
+        let column = match findColumn(widget, &columnSettings.title) {
+            Some(column) => column,
+            None => continue
+        };
+        widget.move_column_after(&column, previousColumnOpt.as_ref());
+        column.set_visible(columnSettings.isVisible);
+        if columnSettings.width != UNSET_WIDTH {
+            column.set_fixed_width(columnSettings.width);
+        }
+        previousColumnOpt = Some(column);
+    }
+}
+
+fn findColumn(widget: &gtk::TreeView, title: &str) -> Option<gtk::TreeViewColumn>
+{
+    widget.columns().into_iter().find(|column| getTitle(column) == title)
+}
+
+fn setupHeaderMenu(widget: &gtk::TreeView)
+{
+    let menu = makeHeaderMenu(widget);
+    menu.set_attach_widget(Some(widget));
+    for column in widget.columns() {
+        let menu = menu.clone();
+        column.button().unwrap().connect_button_press_event(move |_button, event| {
+            if event.event_type() != gdk::EventType::ButtonPress || event.button() != RIGHT_MOUSE_BUTTON {
+                return glib::Propagation::Proceed;
+            }
+            menu.popup_at_pointer(Some(&**event));
+            glib::Propagation::Stop
+        });
+    }
+}
+
+fn makeHeaderMenu(widget: &gtk::TreeView) -> gtk::Menu
+{
+    let menu = gtk::Menu::new();
+    for column in widget.columns() {
+        let item = gtk::CheckMenuItem::with_label(&getTitle(&column));
+        item.set_active(column.is_visible());
+        let columnToToggle = column.clone();
+        item.connect_toggled(move |item| columnToToggle.set_visible(item.is_active()));
+        let itemToUpdate = item.clone();
+        column.connect_visible_notify(move |column| itemToUpdate.set_active(column.is_visible()));
+        menu.append(&item);
+    }
+    menu.show_all();
+    menu
+}
+
+fn connectColumnChanges(widget: &gtk::TreeView, sender: Sender)
+{
+    for column in widget.columns() {
+        let (widget2, sender2) = (widget.clone(), sender.clone());
+        column.connect_visible_notify(move |_column| sendColumnsChanged(&widget2, &sender2));
+        let (widget2, sender2) = (widget.clone(), sender.clone());
+        column.connect_fixed_width_notify(move |_column| sendColumnsChanged(&widget2, &sender2));
+    }
+    widget.connect_columns_changed(move |widget| sendColumnsChanged(widget, &sender));
+}
+
+fn sendColumnsChanged(widget: &gtk::TreeView, sender: &Sender)
+{
+    let settings = widget.columns().iter()
+        .map(|column| CommitLogColumnSettings{
+            title: getTitle(column),
+            isVisible: column.is_visible(),
+            width: column.fixed_width()})
+        .collect();
+    sender.send((Source::CommitLogViewWidget, Event::CommitLogColumnsChanged(settings))).unwrap();
+}
+
+fn getTitle(column: &gtk::TreeViewColumn) -> String
+{
+    column.title().unwrap_or_default().into()
+}
+
+fn defaultWidth() -> i32
+{
+    UNSET_WIDTH
+}
@@ -1,7 +1,10 @@
-use crate::commit_log::CommitLog;
+use crate::commit_annotation::{CommitAnnotation, Hours, parseCreativeWorkPercentage, parseHours};
+use crate::commit_log::{CommitInfo, CommitLog, MarkedBy};
 use crate::commit_log_column::{CommitLogColumn, OriginalRow};
-use crate::event::Event;
-use crate::event_handling::{EventHandler, onUnknown};
+use crate::date_format::DateFormat;
+use crate::error_dialog::showErrorDialog;
+use crate::event::{Event, Marking};
+use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
 use crate::source::Source;
 
@@ -11,17 +14,21 @@ use gtk::prelude::TreeModelExt as _;
 use std::cell::RefCell;
 use std::rc::Rc;
 use to_trait::To as _;
-use time::{format_description::FormatItem, macros::format_description};
 
-const DATE_TIME_FORMAT: &[FormatItem] =
-    format_description!("[day padding:space] [month repr:short] [year] [hour padding:space]:[minute]:[second]");
-const DO_NOT_REPORT_COMMIT: bool = false;
+const MARKED_BY_RULE: &str = "rule";
+const MARKED_MANUALLY: &str = "";
+// sorts commits without hours before the ones with zero hours
+const NO_HOURS_VALUE: Hours = -1.0;
+const NO_CREATIVE_WORK_VALUE: i32 = -1;
 
 
 pub struct CommitLogModel
 {
     commitLog: Rc<RefCell<CommitLog>>,
-    store: gtk::ListStore
+    store: gtk::ListStore,
+    dateFormat: DateFormat,
+    parentWindow: gtk::ApplicationWindow,
+    sender: Sender
 }
 
 impl EventHandler for CommitLogModel
@@ -29,8 +36,13 @@ impl EventHandler for CommitLogModel
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::CommitLogChanged                     => self.onCommitLogChanged(),
-            Event::MarkCommitForReportToggled(treePath) => self.onMarkCommitForReportToggled(treePath),
+            Event::CommitCreativeWorkEdited(treePath, text)          => self.onCommitCreativeWorkEdited(treePath, text),
+            Event::CommitDescriptionEdited(treePath, text)           => self.onCommitDescriptionEdited(treePath, text),
+            Event::CommitHoursEdited(treePath, text)                 => self.onCommitHoursEdited(treePath, text),
+            Event::CommitLogChanged                                  => self.onCommitLogChanged(),
+            Event::DateFormatChanged(format)                         => self.onDateFormatChanged(*format),
+            Event::MarkCommitForReportToggled(treePath)              => self.onMarkCommitForReportToggled(treePath),
+            Event::MarkCommitsForReportRequested(treePaths, marking) => self.onMarkCommitsRequested(treePaths, *marking),
             _ => onUnknown(source, event)
         }
     }
@@ -38,9 +50,16 @@ impl EventHandler for CommitLogModel
 
 impl CommitLogModel
 {
-    pub fn new(commitLog: Rc<RefCell<CommitLog>>, guiElementProvider: &GuiElementProvider) -> Self
+    pub fn new(
+        dateFormat: DateFormat,
+        commitLog: Rc<RefCell<CommitLog>>,
+        guiElementProvider: &GuiElementProvider,
+        sender: Sender)
+        -> Self
     {
-        let newSelf = Self{commitLog, store: guiElementProvider.get::<gtk::ListStore>("commitLogStore")};
+        let store = guiElementProvider.get::<gtk::ListStore>("commitLogStore");
+        let parentWindow = guiElementProvider.get::<gtk::ApplicationWindow>("mainWindow");
+        let newSelf = Self{commitLog, store, dateFormat, parentWindow, sender};
         newSelf.onCommitLogChanged();
         newSelf
     }
@@ -48,28 +67,161 @@ impl CommitLogModel
 
     // private
 
+    // Rows are updated in place, so that the selection and the scroll position stay, unless other commits got loaded.
     fn onCommitLogChanged(&self)
     {
-        self.store.clear();
-        for (row, commit) in self.commitLog.borrow().getCommits().iter().enumerate() {
+        let commitLog = self.commitLog.borrow();
+        let hasSameCommits = self.hasRowsOf(commitLog.getCommits());
+        if !hasSameCommits {
+            self.store.clear();
+        }
+        for (row, commit) in commitLog.getCommits().iter().enumerate() {
+            let (changedLines, changedFiles) = match commit.diffStats {
+                Some(stats) => (
+                    stats.changedLines.try_to::<u32>().unwrap(), stats.changedFiles.try_to::<u32>().unwrap()),
+                None => (0, 0)
+            };
+            let iter = match hasSameCommits {
+                true => self.store.iter_nth_child(None, row.try_to().unwrap()).unwrap(),
+                false => self.store.append()
+            };
             self.store.set(
-                &self.store.append(),
-                &[(CommitLogColumn::Report.into(),      &DO_NOT_REPORT_COMMIT),
-                  (CommitLogColumn::Message.into(),     &commit.summary),
-                  (CommitLogColumn::Date.into(),        &commit.date.format(DATE_TIME_FORMAT).unwrap()),
-                  (CommitLogColumn::Author.into(),      &commit.author),
-                  (CommitLogColumn::Email.into(),       &commit.email),
-                  (CommitLogColumn::OriginalRow.into(), &(row.try_to::<OriginalRow>().unwrap()))]);
+                &iter,
+                &[(CommitLogColumn::Report.into(),            &commit.markedForReport),
+                  (CommitLogColumn::Message.into(),           &commit.summary),
+                  (CommitLogColumn::Date.into(),              &self.dateFormat.formatForLog(&commit.date)),
+                  (CommitLogColumn::Author.into(),            &commit.author),
+                  (CommitLogColumn::Email.into(),             &commit.email),
+                  (CommitLogColumn::Hours.into(),             &commit.annotation.formatHours()),
+                  (CommitLogColumn::CreativeWork.into(),      &commit.annotation.formatCreativeWorkPercentage()),
+                  (CommitLogColumn::Description.into(),       &commit.annotation.description),
+                  (CommitLogColumn::MarkedBy.into(),          &formatMarkedBy(commit.markedBy)),
+                  (CommitLogColumn::ShortId.into(),           &commit.shortId),
+                  (CommitLogColumn::Committer.into(),         &commit.committer),
+                  (CommitLogColumn::LinesChanged.into(),      &changedLines),
+                  (CommitLogColumn::FilesChanged.into(),      &changedFiles),
+                  (CommitLogColumn::CoAuthors.into(),         &commit.formatCoAuthorNames()),
+                  (CommitLogColumn::OriginalRow.into(),       &(row.try_to::<OriginalRow>().unwrap())),
+                  (CommitLogColumn::DateTimestamp.into(),     &commit.date.unix_timestamp()),
+                  (CommitLogColumn::HoursValue.into(),        &getHoursValue(&commit.annotation)),
+                  (CommitLogColumn::CreativeWorkValue.into(), &getCreativeWorkValue(&commit.annotation))]);
+        }
+    }
+
+    fn hasRowsOf(&self, commits: &[CommitInfo]) -> bool
+    {
+        if self.store.iter_n_children(None).try_to::<usize>().unwrap() != commits.len() {
+            return false;
         }
+        commits.iter().enumerate().all(|(row, commit)| {
+            let iter = self.store.iter_nth_child(None, row.try_to().unwrap()).unwrap();
+            self.store.value(&iter, CommitLogColumn::ShortId.into()).get::<String>().unwrap() == commit.shortId
+        })
+    }
+
+    fn onDateFormatChanged(&mut self, format: DateFormat)
+    {
+        self.dateFormat = format;
+        self.onCommitLogChanged();
     }
 
     fn onMarkCommitForReportToggled(&self, treePath: &gtk::TreePath)
+    {
+        self.markCommit(treePath, Marking::Toggle);
+    }
+
+    fn onMarkCommitsRequested(&self, treePaths: &[gtk::TreePath], marking: Marking)
+    {
+        for treePath in treePaths {
+            self.markCommit(treePath, marking);
+        }
+    }
+
+    fn markCommit(&self, treePath: &gtk::TreePath, marking: Marking)
     {
         let iter = self.store.iter(treePath).unwrap();
-        let report = !self.store.value(&iter, CommitLogColumn::Report.into()).get::<bool>().unwrap();
-        self.store.set(&iter, &[(CommitLogColumn::Report.into(), &report)]);
-        let row = self.store.value(&iter, CommitLogColumn::OriginalRow.into()).get::<OriginalRow>().unwrap()
-            .try_into().unwrap();
-        self.commitLog.borrow_mut().setMarkedForReport(row, report);
+        let report = match marking {
+            Marking::Mark => true,
+            Marking::Unmark => false,
+            Marking::Toggle => !self.store.value(&iter, CommitLogColumn::Report.into()).get::<bool>().unwrap()
+        };
+        self.store.set(
+            &iter, &[(CommitLogColumn::Report.into(), &report), (CommitLogColumn::MarkedBy.into(), &MARKED_MANUALLY)]);
+        self.commitLog.borrow_mut().setMarkedForReport(self.getOriginalRow(treePath), report);
     }
+
+    fn onCommitHoursEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let hours = match parseHours(text) {
+            Ok(hours) => hours,
+            Err(e) => return showErrorDialog(&self.parentWindow, &format!("Hours were not changed. {}", e))
+        };
+
+        let row = self.getOriginalRow(treePath);
+        let mut annotation = self.commitLog.borrow().getCommit(row).unwrap().annotation.clone();
+        annotation.hours = hours;
+        self.store.set(
+            &self.store.iter(treePath).unwrap(),
+            &[(CommitLogColumn::Hours.into(),      &annotation.formatHours()),
+              (CommitLogColumn::HoursValue.into(), &getHoursValue(&annotation))]);
+        self.changeAnnotation(row, annotation);
+    }
+
+    fn onCommitCreativeWorkEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let percentage = match parseCreativeWorkPercentage(text) {
+            Ok(percentage) => percentage,
+            Err(e) => return showErrorDialog(&self.parentWindow, &format!("Creative work was not changed. {}", e))
+        };
+
+        let row = self.getOriginalRow(treePath);
+        let mut annotation = self.commitLog.borrow().getCommit(row).unwrap().annotation.clone();
+        annotation.creativeWorkPercentage = percentage;
+        self.store.set(
+            &self.store.iter(treePath).unwrap(),
+            &[(CommitLogColumn::CreativeWork.into(),      &annotation.formatCreativeWorkPercentage()),
+              (CommitLogColumn::CreativeWorkValue.into(), &getCreativeWorkValue(&annotation))]);
+        self.changeAnnotation(row, annotation);
+    }
+
+    fn onCommitDescriptionEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let row = self.getOriginalRow(treePath);
+        let mut annotation = self.commitLog.borrow().getCommit(row).unwrap().annotation.clone();
+        annotation.description = text.trim().into();
+        self.store.set(
+            &self.store.iter(treePath).unwrap(), &[(CommitLogColumn::Description.into(), &annotation.description)]);
+        self.changeAnnotation(row, annotation);
+    }
+
+    fn changeAnnotation(&self, row: usize, annotation: CommitAnnotation)
+    {
+        let commitId = self.commitLog.borrow().getCommit(row).unwrap().id;
+        self.commitLog.borrow_mut().setAnnotation(row, annotation.clone());
+        self.sender.send((Source::CommitLogModel, Event::CommitAnnotationChanged(commitId, annotation))).unwrap();
+    }
+
+    fn getOriginalRow(&self, treePath: &gtk::TreePath) -> usize
+    {
+        let iter = self.store.iter(treePath).unwrap();
+        self.store.value(&iter, CommitLogColumn::OriginalRow.into()).get::<OriginalRow>().unwrap().try_into().unwrap()
+    }
+}
+
+fn formatMarkedBy(markedBy: MarkedBy) -> &'static str
+{
+    match markedBy {
+        MarkedBy::Rule => MARKED_BY_RULE,
+        MarkedBy::Nobody | MarkedBy::User => MARKED_MANUALLY
+    }
+}
+
+fn getHoursValue(annotation: &CommitAnnotation) -> Hours
+{
+    annotation.hours.unwrap_or(NO_HOURS_VALUE)
+}
+
+fn getCreativeWorkValue(annotation: &CommitAnnotation) -> i32
+{
+    annotation.creativeWorkPercentage.map_or(NO_CREATIVE_WORK_VALUE, i32::from)
 }
@@ -1,7 +1,7 @@
 use crate::commit_log::CommitLog;
 use crate::commit_log_column::{CommitLogColumn, OriginalRow};
 use crate::config_store::Config;
-use crate::event::{CommitAuthorFilterStr, Event, Year};
+use crate::event::{CommitAuthorFilterStr, Event, Marking, Year};
 use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
 use crate::source::Source;
@@ -9,6 +9,7 @@ use crate::source::Source;
 use gtk::glib;
 use gtk::prelude::TreeModelExt as _;
 use gtk::prelude::TreeModelFilterExt as _;
+use gtk::prelude::TreeModelSortExt as _;
 use std::cell::RefCell;
 use std::rc::Rc;
 use time::Month;
@@ -17,6 +18,7 @@ use time::Month;
 pub struct CommitLogModelFilter
 {
     modelFilter: gtk::TreeModelFilter,
+    modelSort: gtk::TreeModelSort,
     authorFilter: AuthorFilter,
     monthFilter: MonthFilter,
     yearFilter: YearFilter,
@@ -32,10 +34,14 @@ impl EventHandler for CommitLogModelFilter
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::CommitAuthorFilterChanged(filter)    => self.onCommitAuthorFilterChanged(filter),
-            Event::MarkCommitForReportToggled(treePath) => self.onReportCommitToggled(treePath),
-            Event::MonthFilterChanged(month)            => self.onMonthChanged(*month),
-            Event::YearFilterChanged(year)              => self.onYearChanged(*year),
+            Event::CommitAuthorFilterChanged(filter)                 => self.onCommitAuthorFilterChanged(filter),
+            Event::CommitCreativeWorkEdited(treePath, text)          => self.onCommitCreativeWorkEdited(treePath, text),
+            Event::CommitDescriptionEdited(treePath, text)           => self.onCommitDescriptionEdited(treePath, text),
+            Event::CommitHoursEdited(treePath, text)                 => self.onCommitHoursEdited(treePath, text),
+            Event::MarkCommitForReportToggled(treePath)              => self.onReportCommitToggled(treePath),
+            Event::MarkCommitsForReportRequested(treePaths, marking) => self.onMarkCommitsRequested(treePaths, *marking),
+            Event::MonthFilterChanged(month)                         => self.onMonthChanged(*month),
+            Event::YearFilterChanged(year)                           => self.onYearChanged(*year),
             _ => onUnknown(source, event)
         }
     }
@@ -51,12 +57,13 @@ impl CommitLogModelFilter
         -> Self
     {
         let modelFilter = guiElementProvider.get::<gtk::TreeModelFilter>("commitLogStoreFilter");
+        let modelSort = guiElementProvider.get::<gtk::TreeModelSort>("commitLogStoreSort");
         let authorFilter = Rc::new(RefCell::new(config.commitAuthorFilter.clone()));
         let monthFilter = Rc::new(RefCell::new(Month::January));
         let yearFilter = Rc::new(RefCell::new(1));
         setupFilterFunction(
             commitLog, &modelFilter, Rc::clone(&authorFilter), Rc::clone(&monthFilter), Rc::clone(&yearFilter));
-        Self{modelFilter, authorFilter, monthFilter, yearFilter, sender}
+        Self{modelFilter, modelSort, authorFilter, monthFilter, yearFilter, sender}
     }
 
 
@@ -68,6 +75,33 @@ impl CommitLogModelFilter
         self.modelFilter.refilter();
     }
 
+    fn onCommitCreativeWorkEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let childPath = self.convertToStorePath(treePath);
+        self.sender.send((Source::CommitLogModelFilter, Event::CommitCreativeWorkEdited(childPath, text.into()))).unwrap();
+    }
+
+    fn onCommitDescriptionEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let childPath = self.convertToStorePath(treePath);
+        self.sender.send((Source::CommitLogModelFilter, Event::CommitDescriptionEdited(childPath, text.into()))).unwrap();
+    }
+
+    fn onCommitHoursEdited(&self, treePath: &gtk::TreePath, text: &str)
+    {
+        let childPath = self.convertToStorePath(treePath);
+        self.sender.send((Source::CommitLogModelFilter, Event::CommitHoursEdited(childPath, text.into()))).unwrap();
+    }
+
+    fn onMarkCommitsRequested(&self, treePaths: &[gtk::TreePath], marking: Marking)
+    {
+        let childPaths = treePaths.iter()
+            .map(|treePath| self.convertToStorePath(treePath))
+            .collect();
+        self.sender.send((Source::CommitLogModelFilter, Event::MarkCommitsForReportRequested(childPaths, marking)))
+            .unwrap();
+    }
+
     fn onMonthChanged(&self, month: Month)
     {
         *self.monthFilter.borrow_mut() = month;
@@ -76,7 +110,7 @@ impl CommitLogModelFilter
 
     fn onReportCommitToggled(&self, treePath: &gtk::TreePath)
     {
-        let childPath = self.modelFilter.convert_path_to_child_path(treePath).unwrap();
+        let childPath = self.convertToStorePath(treePath);
         self.sender.send((Source::CommitLogModelFilter, Event::MarkCommitForReportToggled(childPath))).unwrap();
     }
 
@@ -85,6 +119,13 @@ impl CommitLogModelFilter
         *self.yearFilter.borrow_mut() = year;
         self.modelFilter.refilter();
     }
+
+    // The view shows the sorted model, which wraps the filtered model, which wraps the store.
+    fn convertToStorePath(&self, sortedPath: &gtk::TreePath) -> gtk::TreePath
+    {
+        let filteredPath = self.modelSort.convert_path_to_child_path(sortedPath).unwrap();
+        self.modelFilter.convert_path_to_child_path(&filteredPath).unwrap()
+    }
 }
 
 fn setupFilterFunction(
@@ -101,11 +142,12 @@ fn setupFilterFunction(
 
         let originalRow = model.value(iter, CommitLogColumn::OriginalRow.into()).get::<OriginalRow>().unwrap()
             .try_into().unwrap();
-        let date = commitLog.borrow().getCommit(originalRow).unwrap().date;
-        if date.year() != *yearFilter.borrow() {
+        let commitLog = commitLog.borrow();
+        let commit = commitLog.getCommit(originalRow).unwrap();
+        if commit.date.year() != *yearFilter.borrow() {
             return false;
         }
-        if date.month() != *monthFilter.borrow() {
+        if commit.date.month() != *monthFilter.borrow() {
             return false;
         }
 
@@ -113,8 +155,7 @@ fn setupFilterFunction(
         if authorFilter.is_empty() {
             return true;
         }
-        let author = model.value(iter, CommitLogColumn::Author.into()).get::<String>().unwrap();
-        author == *authorFilter
+        commit.isAuthoredBy(authorFilter)
     });
 }
 
@@ -1,6 +1,9 @@
 use crate::commit_log::CommitLog;
 use crate::commit_log_column::{CommitLogColumn, OriginalRow};
-use crate::event::Event;
+use crate::commit_log_columns::{CommitLogColumnsSettings, setupCommitLogColumns};
+use crate::commit_log_view_menu::setupCommitLogViewMenu;
+use crate::config_store::Config;
+use crate::event::{BulkMarking, CommitAuthorFilter, CommitAuthorFilterStr, Event, Marking};
 use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
 use crate::source::Source;
@@ -9,12 +12,20 @@ use crate::tree_view_column_config::{ColumnRenderer, TreeViewColumnConfig};
 
 use gtk::prelude::TreeSelectionExt as _;
 use gtk::prelude::TreeModelExt as _;
+use gtk::prelude::TreeViewColumnExt as _;
+use gtk::prelude::TreeViewExt as _;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 
+const CONTINUE_ITERATING_MODEL: bool = false;
+
 
 pub struct CommitLogView
 {
+    widget: gtk::TreeView,
+    diffStatsColumns: Vec<gtk::TreeViewColumn>,
+    authorFilter: CommitAuthorFilter,
     commitLog: Rc<RefCell<CommitLog>>,
     sender: Sender
 }
@@ -24,7 +35,10 @@ impl EventHandler for CommitLogView
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::SelectionChanged(selection) => self.handleSelectionChanged(selection),
+            Event::BulkMarkingRequested(bulkMarking) => self.onBulkMarkingRequested(*bulkMarking),
+            Event::CommitAuthorFilterChanged(filter) => self.onCommitAuthorFilterChanged(filter),
+            Event::CommitLogColumnsChanged(settings) => self.onCommitLogColumnsChanged(settings),
+            Event::SelectionChanged(selection)       => self.handleSelectionChanged(selection),
             _ => onUnknown(source, event)
         }
     }
@@ -32,7 +46,12 @@ impl EventHandler for CommitLogView
 
 impl CommitLogView
 {
-    pub fn new(commitLog: Rc<RefCell<CommitLog>>, guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    pub fn new(
+        config: &Config,
+        commitLog: Rc<RefCell<CommitLog>>,
+        guiElementProvider: &GuiElementProvider,
+        sender: Sender)
+        -> Self
     {
         TreeView::new(
             guiElementProvider,
@@ -40,7 +59,14 @@ impl CommitLogView
             sender.clone(),
             Source::CommitLogViewWidget,
             makeColumnConfigs(sender.clone()));
-        Self{commitLog, sender}
+        let widget = guiElementProvider.get::<gtk::TreeView>("commitLogView");
+        // columns have to be taken before they get reordered by the restored settings
+        let diffStatsColumns = [CommitLogColumn::LinesChanged, CommitLogColumn::FilesChanged].into_iter()
+            .map(|column| widget.column(column.into()).unwrap())
+            .collect();
+        setupCommitLogViewMenu(&widget, sender.clone());
+        setupCommitLogColumns(&widget, &config.commitLogColumns, sender.clone());
+        Self{widget, diffStatsColumns, authorFilter: config.commitAuthorFilter.clone(), commitLog, sender}
     }
 
 
@@ -48,24 +74,124 @@ impl CommitLogView
 
     fn handleSelectionChanged(&self, selection: &gtk::TreeSelection)
     {
-        match selection.selected() {
-            Some((model, iter)) => {
-                let row = model.value(&iter, CommitLogColumn::OriginalRow.into()).get::<OriginalRow>().unwrap()
-                    .try_into().unwrap();
-                let commitId = self.commitLog.borrow().getCommit(row).unwrap().id;
+        let (paths, model) = selection.selected_rows();
+        match paths.as_slice() {
+            [path] => {
+                let iter = model.iter(path).unwrap();
+                let commitId = self.commitLog.borrow().getCommit(getOriginalRow(&model, &iter)).unwrap().id;
                 self.sender.send((Source::CommitLogView, Event::CommitSelected(commitId))).unwrap();
             },
-            None => self.sender.send((Source::CommitLogView, Event::CommitUnselected)).unwrap()
+            _ => self.sender.send((Source::CommitLogView, Event::CommitUnselected)).unwrap()
+        }
+    }
+
+    fn onCommitLogColumnsChanged(&self, _settings: &CommitLogColumnsSettings)
+    {
+        if self.diffStatsColumns.iter().any(|column| column.is_visible()) {
+            self.sender.send((Source::CommitLogView, Event::CommitDiffStatsRequested)).unwrap();
+        }
+    }
+
+    fn onCommitAuthorFilterChanged(&mut self, filter: &CommitAuthorFilterStr)
+    {
+        self.authorFilter = filter.into();
+    }
+
+    fn onBulkMarkingRequested(&self, bulkMarking: BulkMarking)
+    {
+        let (paths, marking) = match bulkMarking {
+            BulkMarking::MarkAll                  => (self.collectAllPaths(), Marking::Mark),
+            BulkMarking::UnmarkAll                => (self.collectAllPaths(), Marking::Unmark),
+            BulkMarking::InvertAll                => (self.collectAllPaths(), Marking::Toggle),
+            BulkMarking::MarkSelected             => (self.collectSelectedPaths(), Marking::Mark),
+            BulkMarking::UnmarkSelected           => (self.collectSelectedPaths(), Marking::Unmark),
+            BulkMarking::ToggleSelected           => (self.collectSelectedPaths(), Marking::Toggle),
+            BulkMarking::MarkAllBySelectedAuthors => (self.collectPathsOfSelectedAuthors(), Marking::Mark),
+            BulkMarking::MarkAllByFilteredAuthor  => (self.collectPathsOfFilteredAuthor(), Marking::Mark)
+        };
+        if paths.is_empty() {
+            return;
+        }
+        self.sender.send((Source::CommitLogView, Event::MarkCommitsForReportRequested(paths, marking))).unwrap();
+    }
+
+    fn collectAllPaths(&self) -> Vec<gtk::TreePath>
+    {
+        let mut paths = vec![];
+        self.widget.model().unwrap().foreach(|_model, path, _iter| {
+            paths.push(path.clone());
+            CONTINUE_ITERATING_MODEL
+        });
+        paths
+    }
+
+    fn collectSelectedPaths(&self) -> Vec<gtk::TreePath>
+    {
+        self.widget.selection().selected_rows().0
+    }
+
+    // Co-authors count as authors, so pair-programmed commits get marked for each of their authors.
+    fn collectPathsOfSelectedAuthors(&self) -> Vec<gtk::TreePath>
+    {
+        let commitLog = self.commitLog.borrow();
+        let (selectedPaths, model) = self.widget.selection().selected_rows();
+        let authors = selectedPaths.iter()
+            .map(|path| commitLog.getCommit(getOriginalRow(&model, &model.iter(path).unwrap())).unwrap())
+            .flat_map(|commit| {
+                std::iter::once(commit.author.clone())
+                    .chain(commit.coAuthors.iter().map(|coAuthor| coAuthor.name.clone()))
+            })
+            .collect::<HashSet<_>>();
+
+        self.collectPathsOfAuthors(&authors.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    // Nothing is marked without a filter, as marking everything has its own action.
+    fn collectPathsOfFilteredAuthor(&self) -> Vec<gtk::TreePath>
+    {
+        match self.authorFilter.is_empty() {
+            true => vec![],
+            false => self.collectPathsOfAuthors(&[&self.authorFilter])
         }
     }
+
+    fn collectPathsOfAuthors(&self, authors: &[&str]) -> Vec<gtk::TreePath>
+    {
+        let commitLog = self.commitLog.borrow();
+        let mut paths = vec![];
+        self.widget.model().unwrap().foreach(|model, path, iter| {
+            let commit = commitLog.getCommit(getOriginalRow(model, iter)).unwrap();
+            if authors.iter().any(|author| commit.isAuthoredBy(author)) {
+                paths.push(path.clone());
+            }
+            CONTINUE_ITERATING_MODEL
+        });
+        paths
+    }
+}
+
+fn getOriginalRow(model: &gtk::TreeModel, iter: &gtk::TreeIter) -> usize
+{
+    model.value(iter, CommitLogColumn::OriginalRow.into()).get::<OriginalRow>().unwrap().try_into().unwrap()
 }
 
 fn makeColumnConfigs(sender: Sender) -> Vec<TreeViewColumnConfig>
 {
     let indexOfCheckButtonColumn = 0;
-    let mut configs = vec![makeCheckButtonColumnConfig(indexOfCheckButtonColumn, sender)];
-    for index in 1..=4 {
-        configs.push(makeTextColumnConfig(index));
+    let mut configs = vec![makeCheckButtonColumnConfig(indexOfCheckButtonColumn, sender.clone())];
+    configs.push(makeTextColumnConfig(CommitLogColumn::Message.into()));
+    configs.push(makeSortedTextColumnConfig(CommitLogColumn::Date.into(), CommitLogColumn::DateTimestamp.into()));
+    configs.push(makeTextColumnConfig(CommitLogColumn::Author.into()));
+    configs.push(makeTextColumnConfig(CommitLogColumn::Email.into()));
+    configs.push(
+        makeHoursColumnConfig(CommitLogColumn::Hours.into(), CommitLogColumn::HoursValue.into(), sender.clone()));
+    configs.push(makeCreativeWorkColumnConfig(
+        CommitLogColumn::CreativeWork.into(), CommitLogColumn::CreativeWorkValue.into(), sender.clone()));
+    configs.push(makeDescriptionColumnConfig(CommitLogColumn::Description.into(), sender));
+    for column in [CommitLogColumn::MarkedBy, CommitLogColumn::ShortId, CommitLogColumn::Committer,
+                   CommitLogColumn::LinesChanged, CommitLogColumn::FilesChanged,
+                   CommitLogColumn::CoAuthors] {
+        configs.push(makeTextColumnConfig(column.into()));
     }
     configs
 }
@@ -77,11 +203,57 @@ fn makeCheckButtonColumnConfig(index: i32, sender: Sender) -> TreeViewColumnConf
         renderer: ColumnRenderer::CheckButton(Box::new(move |_renderer, treePath| {
             sender.send((Source::CommitLogViewCheckButton, Event::MarkCommitForReportToggled(treePath))).unwrap();
         })),
-        isResizable: false
+        isResizable: false,
+        sortColumnIndex: Some(index)
+    }
+}
+
+// Hours are shown as text, but sorted by their numeric values.
+fn makeHoursColumnConfig(index: i32, sortColumnIndex: i32, sender: Sender) -> TreeViewColumnConfig
+{
+    TreeViewColumnConfig{
+        index,
+        renderer: ColumnRenderer::EditableText(Box::new(move |_renderer, treePath, text| {
+            sender.send((Source::CommitLogViewHoursCell, Event::CommitHoursEdited(treePath, text.into()))).unwrap();
+        })),
+        isResizable: true,
+        sortColumnIndex: Some(sortColumnIndex)
+    }
+}
+
+// Percentages are shown with their sign, but sorted by their numeric values.
+fn makeCreativeWorkColumnConfig(index: i32, sortColumnIndex: i32, sender: Sender) -> TreeViewColumnConfig
+{
+    TreeViewColumnConfig{
+        index,
+        renderer: ColumnRenderer::EditableText(Box::new(move |_renderer, treePath, text| {
+            sender.send((Source::CommitLogViewCreativeWorkCell, Event::CommitCreativeWorkEdited(treePath, text.into())))
+                .unwrap();
+        })),
+        isResizable: true,
+        sortColumnIndex: Some(sortColumnIndex)
+    }
+}
+
+fn makeDescriptionColumnConfig(index: i32, sender: Sender) -> TreeViewColumnConfig
+{
+    TreeViewColumnConfig{
+        index,
+        renderer: ColumnRenderer::EditableText(Box::new(move |_renderer, treePath, text| {
+            sender.send((Source::CommitLogViewDescriptionCell, Event::CommitDescriptionEdited(treePath, text.into())))
+                .unwrap();
+        })),
+        isResizable: true,
+        sortColumnIndex: Some(index)
     }
 }
 
 fn makeTextColumnConfig(index: i32) -> TreeViewColumnConfig
 {
-    TreeViewColumnConfig{index, renderer: ColumnRenderer::Text, isResizable: true}
+    makeSortedTextColumnConfig(index, index)
+}
+
+fn makeSortedTextColumnConfig(index: i32, sortColumnIndex: i32) -> TreeViewColumnConfig
+{
+    TreeViewColumnConfig{index, renderer: ColumnRenderer::Text, isResizable: true, sortColumnIndex: Some(sortColumnIndex)}
 }
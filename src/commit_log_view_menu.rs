@@ -0,0 +1,78 @@
+use crate::event::{BulkMarking, Event};
+use crate::event_handling::Sender;
+use crate::source::Source;
+
+use gtk::gdk;
+use gtk::glib;
+use gtk::prelude::GtkMenuExt as _;
+use gtk::prelude::GtkMenuItemExt as _;
+use gtk::prelude::MenuShellExt as _;
+use gtk::prelude::TreeSelectionExt as _;
+use gtk::prelude::TreeViewExt as _;
+use gtk::prelude::WidgetExt as _;
+
+const RIGHT_MOUSE_BUTTON: u32 = 3;
+
+
+pub fn setupCommitLogViewMenu(widget: &gtk::TreeView, sender: Sender)
+{
+    let menu = makeMenu(sender.clone());
+    menu.set_attach_widget(Some(widget));
+    widget.connect_button_press_event(move |widget, event| {
+        if event.event_type() != gdk::EventType::ButtonPress || event.button() != RIGHT_MOUSE_BUTTON {
+            return glib::Propagation::Proceed;
+        }
+        selectClickedRow(widget, event);
+        menu.popup_at_pointer(Some(&**event));
+        glib::Propagation::Stop
+    });
+
+    widget.connect_key_press_event(move |_widget, event| {
+        if event.keyval() != gdk::keys::constants::space {
+            return glib::Propagation::Proceed;
+        }
+        sender.send((Source::CommitLogViewWidget, Event::BulkMarkingRequested(BulkMarking::ToggleSelected))).unwrap();
+        glib::Propagation::Stop
+    });
+}
+
+// Like in file managers, the menu acts on the clicked row, unless it is a part of the selection.
+fn selectClickedRow(widget: &gtk::TreeView, event: &gdk::EventButton)
+{
+    let (x, y) = event.position();
+    let path = match widget.path_at_pos(x as i32, y as i32) {
+        Some((Some(path), _column, _cellX, _cellY)) => path,
+        _ => return
+    };
+    let selection = widget.selection();
+    if !selection.path_is_selected(&path) {
+        selection.unselect_all();
+        selection.select_path(&path);
+    }
+}
+
+fn makeMenu(sender: Sender) -> gtk::Menu
+{
+    let menu = gtk::Menu::new();
+    addMenuItem(&menu, "Mark all", BulkMarking::MarkAll, sender.clone());
+    addMenuItem(&menu, "Unmark all", BulkMarking::UnmarkAll, sender.clone());
+    addMenuItem(&menu, "Invert marks", BulkMarking::InvertAll, sender.clone());
+    menu.append(&gtk::SeparatorMenuItem::new());
+    addMenuItem(&menu, "Mark selected", BulkMarking::MarkSelected, sender.clone());
+    addMenuItem(&menu, "Unmark selected", BulkMarking::UnmarkSelected, sender.clone());
+    addMenuItem(&menu, "Toggle selected (Space)", BulkMarking::ToggleSelected, sender.clone());
+    menu.append(&gtk::SeparatorMenuItem::new());
+    addMenuItem(&menu, "Mark all by selected authors", BulkMarking::MarkAllBySelectedAuthors, sender.clone());
+    addMenuItem(&menu, "Mark all by filtered author", BulkMarking::MarkAllByFilteredAuthor, sender);
+    menu.show_all();
+    menu
+}
+
+fn addMenuItem(menu: &gtk::Menu, label: &str, bulkMarking: BulkMarking, sender: Sender)
+{
+    let item = gtk::MenuItem::with_label(label);
+    item.connect_activate(move |_item| {
+        sender.send((Source::CommitLogViewMenu, Event::BulkMarkingRequested(bulkMarking))).unwrap();
+    });
+    menu.append(&item);
+}
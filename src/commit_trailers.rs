@@ -0,0 +1,61 @@
+const CO_AUTHORED_BY_KEY: &str = "Co-authored-by";
+
+
+// Trailers are "Key: value" lines at the end of a commit message, like "Co-authored-by: Name <email>".
+pub struct Trailer
+{
+    pub key: String,
+    pub value: String
+}
+
+pub fn parseTrailers(message: &str) -> Vec<Trailer>
+{
+    match git2::message_trailers_strs(message) {
+        Ok(trailers) => trailers.iter()
+            .map(|(key, value)| Trailer{key: key.into(), value: value.into()})
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to parse trailers of a commit message: {}", e);
+            vec![]
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoAuthor
+{
+    pub name: String,
+    pub email: String
+}
+
+impl CoAuthor
+{
+    pub fn format(&self) -> String
+    {
+        match self.email.is_empty() {
+            true => self.name.clone(),
+            false => format!("{} <{}>", self.name, self.email)
+        }
+    }
+}
+
+// Co-authors come from "Co-authored-by: Name <email>" trailers, the key is matched case-insensitively like git does.
+pub fn findCoAuthors(message: &str) -> Vec<CoAuthor>
+{
+    parseTrailers(message).into_iter()
+        .filter(|trailer| trailer.key.eq_ignore_ascii_case(CO_AUTHORED_BY_KEY))
+        .map(|trailer| parseCoAuthor(&trailer.value))
+        .filter(|coAuthor| !coAuthor.name.is_empty())
+        .collect()
+}
+
+fn parseCoAuthor(value: &str) -> CoAuthor
+{
+    match (value.find('<'), value.rfind('>')) {
+        (Some(emailStart), Some(emailEnd)) if emailStart < emailEnd => CoAuthor{
+            name: value[..emailStart].trim().into(),
+            email: value[emailStart + 1..emailEnd].trim().into()
+        },
+        _ => CoAuthor{name: value.trim().into(), email: String::new()}
+    }
+}
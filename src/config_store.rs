@@ -1,11 +1,21 @@
+use crate::auto_marking::AutoMarkingRules;
+use crate::commit_annotation::{CommitAnnotation, CommitAnnotations};
+use crate::commit_log_columns::CommitLogColumnsSettings;
 use crate::config_path::ConfigPath;
+use crate::date_format::DateFormat;
+use crate::diff_colors::DiffColorSettings;
+use crate::diff_settings::DiffSettings;
 use crate::event::{CommitAuthorFilter, Event, OutputPathInfo};
 use crate::event_handling::{EventHandler, onUnknown};
 use crate::pane_with_commit_log_and_diff::PanePosition;
+use crate::report_settings::ReportSettings;
 use crate::repository::Repository;
 use crate::source::Source;
+use crate::text_normalization::TextNormalizationSettings;
+use crate::text_view::FontSize;
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -22,11 +32,21 @@ impl EventHandler for ConfigStore
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::CommitAuthorFilterChanged(filter) => self.onCommitAuthorFilterChanged(filter),
-            Event::OutputPathChanged(pathInfo)       => self.onOutputPathChanged(pathInfo),
-            Event::PanePositionChanged(position)     => self.onPanePositionChanged(*position),
-            Event::RepositoryChanged(repo)           => self.onRepositoryChanged(repo),
-            Event::WindowMaximized(isMaximized)      => self.onWindowMaximized(*isMaximized),
+            Event::AutoMarkingRulesChanged(rules)             => self.onAutoMarkingRulesChanged(rules),
+            Event::CommitAnnotationChanged(id, annotation)    => self.onCommitAnnotationChanged(*id, annotation),
+            Event::CommitAuthorFilterChanged(filter)          => self.onCommitAuthorFilterChanged(filter),
+            Event::CommitLogColumnsChanged(settings)          => self.onCommitLogColumnsChanged(settings),
+            Event::DateFormatChanged(format)                  => self.onDateFormatChanged(*format),
+            Event::DiffColorsChanged(settings)                => self.onDiffColorsChanged(settings),
+            Event::DiffFontSizeChanged(fontSize)              => self.onDiffFontSizeChanged(*fontSize),
+            Event::DiffSettingsChanged(settings)              => self.onDiffSettingsChanged(settings),
+            Event::OutputPathChanged(pathInfo)                => self.onOutputPathChanged(pathInfo),
+            Event::PanePositionChanged(position)              => self.onPanePositionChanged(*position),
+            Event::ReportSettingsChanged(settings)            => self.onReportSettingsChanged(settings),
+            Event::RepositoryChanged(repo)                    => self.onRepositoryChanged(repo),
+            Event::SideBySideDiffToggled(isSideBySide)        => self.onSideBySideDiffToggled(*isSideBySide),
+            Event::TextNormalizationSettingsChanged(settings) => self.onTextNormalizationSettingsChanged(settings),
+            Event::WindowMaximized(isMaximized)               => self.onWindowMaximized(*isMaximized),
             _ => onUnknown(source, event)
         }
     }
@@ -47,6 +67,31 @@ impl ConfigStore
         &self.config
     }
 
+    fn onAutoMarkingRulesChanged(&mut self, rules: &AutoMarkingRules)
+    {
+        if self.config.autoMarkingRules == *rules {
+            return;
+        }
+        self.config.autoMarkingRules = rules.clone();
+        self.saveToFile();
+    }
+
+    fn onCommitAnnotationChanged(&mut self, commitId: git2::Oid, annotation: &CommitAnnotation)
+    {
+        let key = commitId.to_string();
+        if annotation.isEmpty() {
+            if self.config.commitAnnotations.remove(&key).is_none() {
+                return;
+            }
+        } else {
+            if self.config.commitAnnotations.get(&key) == Some(annotation) {
+                return;
+            }
+            self.config.commitAnnotations.insert(key, annotation.clone());
+        }
+        self.saveToFile();
+    }
+
     fn onCommitAuthorFilterChanged(&mut self, filter: &CommitAuthorFilter)
     {
         if self.config.commitAuthorFilter == *filter {
@@ -56,6 +101,51 @@ impl ConfigStore
         self.saveToFile();
     }
 
+    fn onCommitLogColumnsChanged(&mut self, settings: &CommitLogColumnsSettings)
+    {
+        if self.config.commitLogColumns == *settings {
+            return;
+        }
+        self.config.commitLogColumns = settings.clone();
+        self.saveToFile();
+    }
+
+    fn onDateFormatChanged(&mut self, format: DateFormat)
+    {
+        if self.config.dateFormat == format {
+            return;
+        }
+        self.config.dateFormat = format;
+        self.saveToFile();
+    }
+
+    fn onDiffColorsChanged(&mut self, settings: &DiffColorSettings)
+    {
+        if self.config.diffColors == *settings {
+            return;
+        }
+        self.config.diffColors = settings.clone();
+        self.saveToFile();
+    }
+
+    fn onDiffFontSizeChanged(&mut self, fontSize: Option<FontSize>)
+    {
+        if self.config.diffFontSize == fontSize {
+            return;
+        }
+        self.config.diffFontSize = fontSize;
+        self.saveToFile();
+    }
+
+    fn onDiffSettingsChanged(&mut self, settings: &DiffSettings)
+    {
+        if self.config.diffSettings == *settings {
+            return;
+        }
+        self.config.diffSettings = settings.clone();
+        self.saveToFile();
+    }
+
     fn onOutputPathChanged(&mut self, pathInfo: &OutputPathInfo)
     {
         if let Some(prefix) = &self.config.outputPathPrefix {
@@ -77,6 +167,15 @@ impl ConfigStore
         self.saveToFile();
     }
 
+    fn onReportSettingsChanged(&mut self, settings: &ReportSettings)
+    {
+        if self.config.reportSettings == *settings {
+            return;
+        }
+        self.config.reportSettings = settings.clone();
+        self.saveToFile();
+    }
+
     fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
     {
         if let Some(repoPath) = &self.config.repository {
@@ -88,6 +187,32 @@ impl ConfigStore
         self.saveToFile();
     }
 
+    fn onSideBySideDiffToggled(&mut self, isSideBySide: bool)
+    {
+        if self.config.isDiffSideBySide == isSideBySide {
+            return;
+        }
+        self.config.isDiffSideBySide = isSideBySide;
+        self.saveToFile();
+    }
+
+    fn onTextNormalizationSettingsChanged(&mut self, settings: &TextNormalizationSettings)
+    {
+        let repoPath = match &self.config.repository {
+            Some(repoPath) => repoPath,
+            None => return
+        };
+        if self.config.getTextNormalizationSettings() == *settings {
+            return;
+        }
+        if settings.isEnabled() {
+            self.config.textNormalization.insert(repoPath.clone(), settings.clone());
+        } else {
+            self.config.textNormalization.remove(repoPath);
+        }
+        self.saveToFile();
+    }
+
     fn onWindowMaximized(&mut self, isMaximized: bool)
     {
         if self.config.isWindowMaximized == isMaximized {
@@ -108,16 +233,49 @@ impl ConfigStore
 #[derive(Deserialize, Serialize)]
 pub struct Config
 {
+    #[serde(default)]
+    pub autoMarkingRules: AutoMarkingRules,
+    #[serde(default)]
+    pub commitAnnotations: CommitAnnotations,
     #[serde(default)]
     pub commitAuthorFilter: CommitAuthorFilter,
     #[serde(default)]
+    pub commitLogColumns: CommitLogColumnsSettings,
+    #[serde(default)]
+    pub dateFormat: DateFormat,
+    #[serde(default)]
+    pub diffColors: DiffColorSettings,
+    // None when the default font size of the theme is used
+    #[serde(default)]
+    pub diffFontSize: Option<FontSize>,
+    #[serde(default)]
+    pub diffSettings: DiffSettings,
+    #[serde(default)]
+    pub isDiffSideBySide: bool,
+    #[serde(default)]
     pub isWindowMaximized: bool,
     #[serde(default)]
     pub outputPathPrefix: Option<PathBuf>,
     #[serde(default = "defaultPositionOfPaneWithCommitLogAndDiff")]
     pub positionOfPaneWithCommitLogAndDiff: PanePosition,
     #[serde(default)]
-    pub repository: Option<PathBuf>
+    pub reportSettings: ReportSettings,
+    #[serde(default)]
+    pub repository: Option<PathBuf>,
+    // settings of repositories, which differ from the defaults, keyed by their paths
+    #[serde(default)]
+    pub textNormalization: BTreeMap<PathBuf, TextNormalizationSettings>
+}
+
+impl Config
+{
+    pub fn getTextNormalizationSettings(&self) -> TextNormalizationSettings
+    {
+        self.repository.as_ref()
+            .and_then(|repoPath| self.textNormalization.get(repoPath))
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 fn defaultPositionOfPaneWithCommitLogAndDiff() -> PanePosition
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::{Iso8601, Rfc2822};
+use time::{format_description::FormatItem, macros::format_description, Duration, OffsetDateTime};
+
+const COMPACT_FORMAT: &[FormatItem] =
+    format_description!("[day padding:space] [month repr:short] [year] [hour padding:space]:[minute]:[second]");
+const LOCALE_LIKE_FORMAT: &[FormatItem] =
+    format_description!("[weekday], [day padding:none] [month repr:long] [year], [hour]:[minute]:[second]");
+const SECONDS_IN_MINUTE: i64 = 60;
+const SECONDS_IN_HOUR: i64 = 60 * SECONDS_IN_MINUTE;
+const SECONDS_IN_DAY: i64 = 24 * SECONDS_IN_HOUR;
+const SECONDS_IN_WEEK: i64 = 7 * SECONDS_IN_DAY;
+const SECONDS_IN_MONTH: i64 = 30 * SECONDS_IN_DAY;
+const SECONDS_IN_YEAR: i64 = 365 * SECONDS_IN_DAY;
+
+
+// Default keeps the formats used before the format became configurable:
+// a compact one in the commit log and RFC 2822 in diffs.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum DateFormat
+{
+    #[default]
+    Default,
+    Iso8601,
+    LocaleLike,
+    Relative
+}
+
+impl DateFormat
+{
+    pub const ALL: [DateFormat; 4] = [Self::Default, Self::Iso8601, Self::LocaleLike, Self::Relative];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Default    => "default",
+            Self::Iso8601    => "iso8601",
+            Self::LocaleLike => "localeLike",
+            Self::Relative   => "relative"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Default    => "Default",
+            Self::Iso8601    => "ISO 8601",
+            Self::LocaleLike => "Locale-like",
+            Self::Relative   => "Relative"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|format| format.getId() == id)
+    }
+
+    pub fn formatForLog(&self, dateTime: &OffsetDateTime) -> String
+    {
+        match self {
+            Self::Default    => dateTime.format(COMPACT_FORMAT).unwrap(),
+            Self::Iso8601    => dateTime.format(&Iso8601::DEFAULT).unwrap(),
+            Self::LocaleLike => dateTime.format(LOCALE_LIKE_FORMAT).unwrap(),
+            Self::Relative   => formatRelative(OffsetDateTime::now_utc() - *dateTime)
+        }
+    }
+
+    pub fn formatForDiff(&self, dateTime: &OffsetDateTime) -> String
+    {
+        match self {
+            Self::Default => dateTime.format(&Rfc2822).unwrap(),
+            _ => self.formatForLog(dateTime)
+        }
+    }
+
+    // Relative dates change over time, so reports fall back to the default absolute format,
+    // which keeps regenerated reports identical.
+    pub fn formatForReport(&self, dateTime: &OffsetDateTime) -> String
+    {
+        match self {
+            Self::Relative => Self::Default.formatForDiff(dateTime),
+            _ => self.formatForDiff(dateTime)
+        }
+    }
+}
+
+fn formatRelative(elapsed: Duration) -> String
+{
+    let seconds = elapsed.whole_seconds();
+    if seconds < 0 {
+        return "in the future".into();
+    }
+
+    let units = [
+        (SECONDS_IN_YEAR, "year"),
+        (SECONDS_IN_MONTH, "month"),
+        (SECONDS_IN_WEEK, "week"),
+        (SECONDS_IN_DAY, "day"),
+        (SECONDS_IN_HOUR, "hour"),
+        (SECONDS_IN_MINUTE, "minute")];
+    for (secondsInUnit, unitName) in units {
+        let count = seconds / secondsInUnit;
+        match count {
+            0 => continue,
+            1 => return format!("1 {} ago", unitName),
+            _ => return format!("{} {}s ago", count, unitName)
+        }
+    }
+    "just now".into()
+}
@@ -1,8 +1,13 @@
+use crate::diff_colors::{DiffColors, DiffHighlighting};
 use crate::line_number::LineNumber;
+use crate::syntax_highlighter::{LineState, SyntaxHighlighter};
 use crate::text_view::TextView;
+use crate::word_diff::diffWords;
 
 use gtk::prelude::TextTagExt as _;
 
+pub const FILE_HEADER_PREFIX: &str = "diff --git ";
+
 
 pub struct DiffColorizer
 {
@@ -10,6 +15,9 @@ pub struct DiffColorizer
     removedLineTag: gtk::TextTag,
     hunkHeaderTag: gtk::TextTag,
     fileHeaderTag: gtk::TextTag,
+    removedWordTag: gtk::TextTag,
+    addedWordTag: gtk::TextTag,
+    syntaxHighlighter: SyntaxHighlighter,
     tagStartLine: LineNumber,
     state: State
 }
@@ -24,24 +32,46 @@ enum State
 
 impl DiffColorizer
 {
-    pub fn new() -> Self
+    pub fn new(colors: &DiffColors) -> Self
     {
-        let addedLineTag = makeTag("green");
-        let removedLineTag = makeTag("red");
-        let hunkHeaderTag = makeTag("silver");
-        let fileHeaderTag = makeTag("dodgerblue");
-        Self{
-            addedLineTag,
-            removedLineTag,
-            hunkHeaderTag,
-            fileHeaderTag,
+        let newSelf = Self{
+            addedLineTag: gtk::TextTag::new(Some("addedLine")),
+            removedLineTag: gtk::TextTag::new(Some("removedLine")),
+            hunkHeaderTag: gtk::TextTag::new(Some("hunkHeader")),
+            fileHeaderTag: gtk::TextTag::new(Some("fileHeader")),
+            removedWordTag: gtk::TextTag::new(Some("removedWord")),
+            addedWordTag: gtk::TextTag::new(Some("addedWord")),
+            syntaxHighlighter: SyntaxHighlighter::new(colors),
             tagStartLine: 0.into(),
-            state: State::Normal}
+            state: State::Normal};
+        newSelf.setColors(colors);
+        newSelf
+    }
+
+    // Tags are updated in place, so the colors of an already shown diff change as well.
+    pub fn setColors(&self, colors: &DiffColors)
+    {
+        setChangedLineColors(
+            &self.addedLineTag, &colors.addedLineForeground, &colors.addedLineBackground, colors.highlighting);
+        setChangedLineColors(
+            &self.removedLineTag, &colors.removedLineForeground, &colors.removedLineBackground, colors.highlighting);
+        self.hunkHeaderTag.set_foreground(Some(&colors.hunkHeader));
+        self.fileHeaderTag.set_foreground(Some(&colors.fileHeader));
+        self.removedWordTag.set_background(Some(&colors.removedWord));
+        self.addedWordTag.set_background(Some(&colors.addedWord));
+        self.syntaxHighlighter.setColors(colors);
     }
 
     pub fn setupTextView(&self, textView: &TextView)
     {
-        textView.registerTags(&[&self.addedLineTag, &self.removedLineTag, &self.hunkHeaderTag, &self.fileHeaderTag]);
+        textView.registerTags(&[
+            &self.addedLineTag,
+            &self.removedLineTag,
+            &self.hunkHeaderTag,
+            &self.fileHeaderTag,
+            &self.removedWordTag,
+            &self.addedWordTag]);
+        self.syntaxHighlighter.setupTextView(textView);
     }
 
     pub fn colorize(&mut self, textView: &TextView, diff: &str)
@@ -59,18 +89,23 @@ impl DiffColorizer
         self.tagStartLine = 0.into();
         self.applyTagsBasedOnLineTypes(textView, text);
         self.closeLastOpenTag(textView);
+        self.applyWordTags(textView, text);
+        self.applySyntaxTags(textView, text);
     }
 
     fn applyTagsBasedOnLineTypes(&mut self, textView: &TextView, text: &str)
     {
-        for (lineNumber, line) in text.lines().enumerate() {
+        // the commit summary before the first file is left as it is
+        let diffLines = text.lines().enumerate()
+            .skip_while(|(_lineNumber, line)| !line.starts_with(FILE_HEADER_PREFIX));
+        for (lineNumber, line) in diffLines {
             if let Some(character) = line.chars().next() {
                 let lineNumber: LineNumber = lineNumber.into();
                 match character {
                     '+' => self.applyTagToAddedLine(textView, lineNumber),
                     '-' => self.applyTagToRemovedLine(textView, lineNumber),
                     '@' => self.applyTagToHunkHeader(textView, lineNumber),
-                    ' ' | 'C' | 'A' | 'D' => self.applyTagToNormalLine(textView, lineNumber), // space, Commit, Author, Date
+                    ' ' => self.applyTagToNormalLine(textView, lineNumber),
                      _  => self.applyTagToFileHeader(textView, lineNumber),
                 }
             }
@@ -182,6 +217,73 @@ impl DiffColorizer
         }
     }
 
+    // Removed lines are paired with the added lines following them, the first with the first and so on.
+    fn applyWordTags(&self, textView: &TextView, text: &str)
+    {
+        let mut isInsideHunk = false;
+        let mut removedLines = vec![];
+        let mut addedLines = vec![];
+        for (lineNumber, line) in text.lines().enumerate() {
+            let lineNumber: LineNumber = lineNumber.into();
+            match line.chars().next() {
+                Some('-') if isInsideHunk => {
+                    if !addedLines.is_empty() {
+                        self.applyWordTagsToPairedLines(textView, &removedLines, &addedLines);
+                        removedLines.clear();
+                        addedLines.clear();
+                    }
+                    removedLines.push((lineNumber, line));
+                },
+                Some('+') if isInsideHunk => addedLines.push((lineNumber, line)),
+                character => {
+                    self.applyWordTagsToPairedLines(textView, &removedLines, &addedLines);
+                    removedLines.clear();
+                    addedLines.clear();
+                    // file headers contain lines starting with "---" and "+++", so they must not be paired
+                    isInsideHunk = matches!(character, Some('@' | ' ' | '\\'));
+                }
+            }
+        }
+        self.applyWordTagsToPairedLines(textView, &removedLines, &addedLines);
+    }
+
+    fn applyWordTagsToPairedLines(
+        &self, textView: &TextView, removedLines: &[(LineNumber, &str)], addedLines: &[(LineNumber, &str)])
+    {
+        for ((removedLineNumber, removedLine), (addedLineNumber, addedLine)) in removedLines.iter().zip(addedLines) {
+            // the first character is the "-" or "+" prefix
+            let wordDiff = diffWords(&removedLine[1..], &addedLine[1..]);
+            for range in wordDiff.removed {
+                textView.applyTagInLine(&self.removedWordTag, *removedLineNumber, &((range.start + 1)..(range.end + 1)));
+            }
+            for range in wordDiff.added {
+                textView.applyTagInLine(&self.addedWordTag, *addedLineNumber, &((range.start + 1)..(range.end + 1)));
+            }
+        }
+    }
+
+    fn applySyntaxTags(&self, textView: &TextView, text: &str)
+    {
+        let mut isInsideHunk = false;
+        let mut lineState = LineState::default();
+        for (lineNumber, line) in text.lines().enumerate() {
+            match line.chars().next() {
+                Some('+' | '-' | ' ') if isInsideHunk => {
+                    // the first character is the "-", "+" or " " prefix
+                    self.syntaxHighlighter.highlightLine(textView, lineNumber.into(), line, 1, &mut lineState);
+                },
+                Some('@' | '\\') => isInsideHunk = true,
+                _ => {
+                    isInsideHunk = false;
+                    // file headers start with "diff --git a/<old path> b/<new path>"
+                    if let Some(path) = line.strip_prefix(FILE_HEADER_PREFIX).and_then(getNewPath) {
+                        lineState = LineState::forFile(path);
+                    }
+                }
+            }
+        }
+    }
+
     fn closeLastOpenTag(&self, textView: &TextView)
     {
         match self.state {
@@ -193,9 +295,29 @@ impl DiffColorizer
     }
 }
 
-fn makeTag(name: &str) -> gtk::TextTag
+pub fn setChangedLineColors(
+    tag: &gtk::TextTag, foregroundColor: &str, backgroundColor: &str, highlighting: DiffHighlighting)
 {
-    let tag = gtk::TextTag::new(Some(name));
-    tag.set_foreground(Some(name));
+    match highlighting {
+        DiffHighlighting::Foreground => {
+            tag.set_foreground(Some(foregroundColor));
+            tag.set_paragraph_background_set(false);
+        },
+        DiffHighlighting::Background => {
+            tag.set_foreground_set(false);
+            tag.set_paragraph_background(Some(backgroundColor));
+        }
+    }
+}
+
+pub fn makeCollapsedSectionTag() -> gtk::TextTag
+{
+    let tag = gtk::TextTag::new(Some("collapsedSection"));
+    tag.set_invisible(true);
     tag
 }
+
+fn getNewPath(paths: &str) -> Option<&str>
+{
+    paths.rsplit_once(" b/").map(|(_oldPath, newPath)| newPath)
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum DiffColorTheme
+{
+    // light or dark, depending on the current GTK theme
+    #[default]
+    Automatic,
+    Light,
+    Dark
+}
+
+impl DiffColorTheme
+{
+    pub const ALL: [DiffColorTheme; 3] = [Self::Automatic, Self::Light, Self::Dark];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Automatic => "automatic",
+            Self::Light     => "light",
+            Self::Dark      => "dark"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Automatic => "Automatic",
+            Self::Light     => "Light",
+            Self::Dark      => "Dark"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|theme| theme.getId() == id)
+    }
+}
+
+// How added and removed lines stand out from the rest of the diff.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum DiffHighlighting
+{
+    #[default]
+    Foreground,
+    Background
+}
+
+impl DiffHighlighting
+{
+    pub const ALL: [DiffHighlighting; 2] = [Self::Foreground, Self::Background];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Foreground => "foreground",
+            Self::Background => "background"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Foreground => "Text color",
+            Self::Background => "Background color"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|highlighting| highlighting.getId() == id)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct DiffColorSettings
+{
+    pub theme: DiffColorTheme,
+    pub highlighting: DiffHighlighting,
+    // can only be set in the config file, they take precedence over colors of the theme
+    pub customColors: CustomDiffColors
+}
+
+// Colors are given in any format understood by GTK, like "red" or "#ff0000".
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CustomDiffColors
+{
+    pub addedLineForeground: Option<String>,
+    pub addedLineBackground: Option<String>,
+    pub removedLineForeground: Option<String>,
+    pub removedLineBackground: Option<String>,
+    pub hunkHeader: Option<String>,
+    pub fileHeader: Option<String>,
+    pub addedWord: Option<String>,
+    pub removedWord: Option<String>,
+    pub filler: Option<String>,
+    pub syntaxKeyword: Option<String>,
+    pub syntaxString: Option<String>,
+    pub syntaxComment: Option<String>,
+    pub syntaxNumber: Option<String>
+}
+
+pub struct DiffColors
+{
+    pub highlighting: DiffHighlighting,
+    pub addedLineForeground: String,
+    pub addedLineBackground: String,
+    pub removedLineForeground: String,
+    pub removedLineBackground: String,
+    pub hunkHeader: String,
+    pub fileHeader: String,
+    pub addedWord: String,
+    pub removedWord: String,
+    // empty lines which keep both sides of a side-by-side diff aligned
+    pub filler: String,
+    pub syntaxKeyword: String,
+    pub syntaxString: String,
+    pub syntaxComment: String,
+    pub syntaxNumber: String
+}
+
+impl DiffColors
+{
+    pub fn new(settings: &DiffColorSettings, isGtkThemeDark: bool) -> Self
+    {
+        let isDark = match settings.theme {
+            DiffColorTheme::Automatic => isGtkThemeDark,
+            DiffColorTheme::Light => false,
+            DiffColorTheme::Dark => true
+        };
+        let mut colors = match isDark {
+            true => makeDarkColors(settings.highlighting),
+            false => makeLightColors(settings.highlighting)
+        };
+        colors.applyCustomColors(&settings.customColors);
+        colors
+    }
+
+
+    // private
+
+    fn applyCustomColors(&mut self, custom: &CustomDiffColors)
+    {
+        overrideColor(&mut self.addedLineForeground, &custom.addedLineForeground);
+        overrideColor(&mut self.addedLineBackground, &custom.addedLineBackground);
+        overrideColor(&mut self.removedLineForeground, &custom.removedLineForeground);
+        overrideColor(&mut self.removedLineBackground, &custom.removedLineBackground);
+        overrideColor(&mut self.hunkHeader, &custom.hunkHeader);
+        overrideColor(&mut self.fileHeader, &custom.fileHeader);
+        overrideColor(&mut self.addedWord, &custom.addedWord);
+        overrideColor(&mut self.removedWord, &custom.removedWord);
+        overrideColor(&mut self.filler, &custom.filler);
+        overrideColor(&mut self.syntaxKeyword, &custom.syntaxKeyword);
+        overrideColor(&mut self.syntaxString, &custom.syntaxString);
+        overrideColor(&mut self.syntaxComment, &custom.syntaxComment);
+        overrideColor(&mut self.syntaxNumber, &custom.syntaxNumber);
+    }
+}
+
+fn makeLightColors(highlighting: DiffHighlighting) -> DiffColors
+{
+    DiffColors{
+        highlighting,
+        addedLineForeground: "green".into(),
+        addedLineBackground: "#e6ffed".into(),
+        removedLineForeground: "red".into(),
+        removedLineBackground: "#ffeef0".into(),
+        hunkHeader: "silver".into(),
+        fileHeader: "dodgerblue".into(),
+        addedWord: "lightgreen".into(),
+        removedWord: "lightpink".into(),
+        filler: "lightgray".into(),
+        syntaxKeyword: "darkorchid".into(),
+        syntaxString: "darkgoldenrod".into(),
+        syntaxComment: "gray".into(),
+        syntaxNumber: "teal".into()
+    }
+}
+
+fn makeDarkColors(highlighting: DiffHighlighting) -> DiffColors
+{
+    DiffColors{
+        highlighting,
+        addedLineForeground: "#7ee787".into(),
+        addedLineBackground: "#1c3426".into(),
+        removedLineForeground: "#ff7b72".into(),
+        removedLineBackground: "#3d1f22".into(),
+        hunkHeader: "#8b949e".into(),
+        fileHeader: "#58a6ff".into(),
+        addedWord: "#236b35".into(),
+        removedWord: "#8e2a2f".into(),
+        filler: "#30363d".into(),
+        syntaxKeyword: "#d2a8ff".into(),
+        syntaxString: "#e3b341".into(),
+        syntaxComment: "#8b949e".into(),
+        syntaxNumber: "#79c0ff".into()
+    }
+}
+
+fn overrideColor(color: &mut String, customColor: &Option<String>)
+{
+    if let Some(customColor) = customColor {
+        *color = customColor.clone();
+    }
+}
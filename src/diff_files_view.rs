@@ -0,0 +1,141 @@
+use crate::event::Event;
+use crate::event_handling::Sender;
+use crate::gui_element_provider::GuiElementProvider;
+use crate::source::Source;
+use crate::tree_view::TreeView;
+use crate::tree_view_column_config::{ColumnRenderer, TreeViewColumnConfig};
+
+use gtk::prelude::GtkListStoreExt as _;
+use gtk::prelude::GtkListStoreExtManual as _;
+use gtk::prelude::TreeModelExt as _;
+use gtk::prelude::TreeSelectionExt as _;
+use std::collections::HashSet;
+use to_trait::To as _;
+
+pub type FileIndex = usize;
+
+
+// Lists files changed in a commit, in the order in which they appear in the diff.
+pub struct DiffFilesView
+{
+    store: gtk::ListStore
+}
+
+impl DiffFilesView
+{
+    pub fn new(guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    {
+        TreeView::new(
+            guiElementProvider,
+            "commitDiffFilesView",
+            sender.clone(),
+            Source::CommitDiffFilesViewWidget,
+            makeColumnConfigs(sender));
+        Self{store: guiElementProvider.get::<gtk::ListStore>("commitDiffFilesStore")}
+    }
+
+    pub fn show(&self, diff: &git2::Diff, collapsedFiles: &HashSet<FileIndex>)
+    {
+        self.store.clear();
+        for (index, delta) in diff.deltas().enumerate() {
+            let (added, removed) = match git2::Patch::from_diff(diff, index).unwrap() {
+                Some(patch) => {
+                    let (_context, additions, deletions) = patch.line_stats().unwrap();
+                    (additions, deletions)
+                },
+                None => (0, 0)
+            };
+            let path = delta.new_file().path().or(delta.old_file().path()).unwrap().to_string_lossy().to_string();
+            self.store.set(
+                &self.store.append(),
+                &[(DiffFilesColumn::Shown.into(),   &!collapsedFiles.contains(&index)),
+                  (DiffFilesColumn::Status.into(),  &formatStatus(delta.status())),
+                  (DiffFilesColumn::Path.into(),    &path),
+                  (DiffFilesColumn::Added.into(),   &format!("+{}", added)),
+                  (DiffFilesColumn::Removed.into(), &format!("-{}", removed))]);
+        }
+    }
+
+    pub fn clear(&self)
+    {
+        self.store.clear();
+    }
+
+    // Returns the index of the toggled file and whether it is shown now.
+    pub fn toggleShown(&self, treePath: &gtk::TreePath) -> (FileIndex, bool)
+    {
+        let iter = self.store.iter(treePath).unwrap();
+        let isShown = !self.store.value(&iter, DiffFilesColumn::Shown.into()).get::<bool>().unwrap();
+        self.store.set(&iter, &[(DiffFilesColumn::Shown.into(), &isShown)]);
+        (getFileIndex(treePath), isShown)
+    }
+}
+
+pub fn getSelectedFile(selection: &gtk::TreeSelection) -> Option<FileIndex>
+{
+    let (paths, _model) = selection.selected_rows();
+    match paths.as_slice() {
+        [path] => Some(getFileIndex(path)),
+        _ => None
+    }
+}
+
+fn getFileIndex(treePath: &gtk::TreePath) -> FileIndex
+{
+    treePath.indices()[0].try_to::<FileIndex>().unwrap()
+}
+
+fn formatStatus(status: git2::Delta) -> &'static str
+{
+    match status {
+        git2::Delta::Added      => "A",
+        git2::Delta::Deleted    => "D",
+        git2::Delta::Modified   => "M",
+        git2::Delta::Renamed    => "R",
+        git2::Delta::Copied     => "C",
+        git2::Delta::Typechange => "T",
+        _ => "?"
+    }
+}
+
+enum DiffFilesColumn
+{
+    Shown,
+    Status,
+    Path,
+    Added,
+    Removed
+}
+
+impl From<DiffFilesColumn> for u32
+{
+    fn from(value: DiffFilesColumn) -> Self
+    {
+        value as Self
+    }
+}
+
+impl From<DiffFilesColumn> for i32
+{
+    fn from(value: DiffFilesColumn) -> Self
+    {
+        value as Self
+    }
+}
+
+fn makeColumnConfigs(sender: Sender) -> Vec<TreeViewColumnConfig>
+{
+    let mut configs = vec![TreeViewColumnConfig{
+        index: DiffFilesColumn::Shown.into(),
+        renderer: ColumnRenderer::CheckButton(Box::new(move |_renderer, treePath| {
+            sender.send((Source::CommitDiffFilesViewCheckButton, Event::DiffFileSectionToggled(treePath))).unwrap();
+        })),
+        isResizable: false,
+        sortColumnIndex: None
+    }];
+    for column in [DiffFilesColumn::Status, DiffFilesColumn::Path, DiffFilesColumn::Added, DiffFilesColumn::Removed] {
+        configs.push(TreeViewColumnConfig{
+            index: column.into(), renderer: ColumnRenderer::Text, isResizable: true, sortColumnIndex: None});
+    }
+    configs
+}
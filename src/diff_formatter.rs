@@ -12,12 +12,17 @@ impl DiffFormatter
 
     pub fn format(&mut self, line: &git2::DiffLine) -> bool
     {
-        let lineContent = String::from_utf8_lossy(line.content());
+        self.formatWithContent(line, &String::from_utf8_lossy(line.content()))
+    }
+
+    // Content of lines can be decoded by the caller, e.g. from another encoding than UTF-8.
+    pub fn formatWithContent(&mut self, line: &git2::DiffLine, lineContent: &str) -> bool
+    {
         match line.origin() {
             // on nightly this could be: prefix @ ('+' | '-' | ' ')
-            prefix if ['+', '-', ' '].contains(&prefix) => self.addContent(prefix, &lineContent),
-            'F' => self.addFileHeader(&lineContent),
-            _  => self.addHunkInfo(&lineContent)
+            prefix if ['+', '-', ' '].contains(&prefix) => self.addContent(prefix, lineContent),
+            'F' => self.addFileHeader(lineContent),
+            _  => self.addHunkInfo(lineContent)
         };
         FORMATTING_SUCCEEDED
     }
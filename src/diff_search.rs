@@ -0,0 +1,242 @@
+use crate::event::Event;
+use crate::event_handling::Sender;
+use crate::gui_element_provider::GuiElementProvider;
+use crate::line_number::LineNumber;
+use crate::source::Source;
+use crate::text_view::TextView;
+use crate::word_diff::CharRange;
+
+use gtk::gdk;
+use gtk::glib;
+use gtk::prelude::ButtonExt as _;
+use gtk::prelude::EntryExt as _;
+use gtk::prelude::LabelExt as _;
+use gtk::prelude::SearchBarExt as _;
+use gtk::prelude::SearchEntryExt as _;
+use gtk::prelude::ToggleButtonExt as _;
+use gtk::prelude::WidgetExt as _;
+use regex::{Regex, RegexBuilder};
+
+
+#[derive(Clone, Debug, Default)]
+pub struct DiffSearchQuery
+{
+    pub text: String,
+    pub isCaseSensitive: bool,
+    pub isRegex: bool
+}
+
+// Finds matches of a query in the shown text views of a diff, with one of the matches being the current one.
+pub struct DiffSearch
+{
+    statusLabel: gtk::Label,
+    query: DiffSearchQuery,
+    matches: Vec<SearchMatch>,
+    currentMatchIndex: Option<usize>
+}
+
+struct SearchMatch
+{
+    textViewIndex: usize,
+    line: usize,
+    charRange: CharRange
+}
+
+impl DiffSearch
+{
+    pub fn new(guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    {
+        setupSearchBar(guiElementProvider, sender);
+        Self{
+            statusLabel: guiElementProvider.get::<gtk::Label>("commitDiffSearchStatusLabel"),
+            query: DiffSearchQuery::default(),
+            matches: vec![],
+            currentMatchIndex: None
+        }
+    }
+
+    pub fn setQuery(&mut self, query: &DiffSearchQuery)
+    {
+        self.query = query.clone();
+    }
+
+    // Has to be called also whenever the text of the views changes.
+    pub fn search(&mut self, textViews: &[&TextView])
+    {
+        self.matches.clear();
+        self.currentMatchIndex = None;
+        let regex = match self.makeRegex() {
+            Ok(Some(regex)) => regex,
+            Ok(None) => {
+                self.highlightMatches(textViews);
+                self.statusLabel.set_text("");
+                return;
+            },
+            Err(e) => {
+                self.highlightMatches(textViews);
+                self.statusLabel.set_text(&e);
+                return;
+            }
+        };
+
+        for (textViewIndex, textView) in textViews.iter().enumerate() {
+            for (line, lineText) in textView.getText().lines().enumerate() {
+                self.matches.extend(findMatchesInLine(&regex, lineText).into_iter()
+                    .map(|charRange| SearchMatch{textViewIndex, line, charRange}));
+            }
+        }
+        // matches of both sides of a side-by-side diff are visited from the top down
+        self.matches.sort_by_key(|found| (found.line, found.textViewIndex, found.charRange.start));
+        self.highlightMatches(textViews);
+        self.goToMatch(textViews, 0);
+    }
+
+    pub fn goToNextMatch(&mut self, textViews: &[&TextView])
+    {
+        let index = match self.currentMatchIndex {
+            Some(index) => (index + 1) % self.matches.len(),
+            None => 0
+        };
+        self.goToMatch(textViews, index);
+    }
+
+    pub fn goToPreviousMatch(&mut self, textViews: &[&TextView])
+    {
+        let index = match self.currentMatchIndex {
+            Some(0) | None => self.matches.len().saturating_sub(1),
+            Some(index) => index - 1
+        };
+        self.goToMatch(textViews, index);
+    }
+
+
+    // private
+
+    fn makeRegex(&self) -> Result<Option<Regex>, String>
+    {
+        if self.query.text.is_empty() {
+            return Ok(None);
+        }
+        let pattern = match self.query.isRegex {
+            true => self.query.text.clone(),
+            false => regex::escape(&self.query.text)
+        };
+        match RegexBuilder::new(&pattern).case_insensitive(!self.query.isCaseSensitive).build() {
+            Ok(regex) => Ok(Some(regex)),
+            Err(e) => Err(format!("Invalid regular expression: {}", e))
+        }
+    }
+
+    fn highlightMatches(&self, textViews: &[&TextView])
+    {
+        for (textViewIndex, textView) in textViews.iter().enumerate() {
+            let matches = self.matches.iter()
+                .filter(|searchMatch| searchMatch.textViewIndex == textViewIndex)
+                .map(|searchMatch| (LineNumber(searchMatch.line), searchMatch.charRange.clone()))
+                .collect::<Vec<_>>();
+            textView.highlightSearchMatches(&matches);
+        }
+    }
+
+    fn goToMatch(&mut self, textViews: &[&TextView], index: usize)
+    {
+        for textView in textViews {
+            textView.clearCurrentSearchMatch();
+        }
+        let searchMatch = match self.matches.get(index) {
+            Some(searchMatch) => searchMatch,
+            None => {
+                self.currentMatchIndex = None;
+                self.statusLabel.set_text(if self.query.text.is_empty() { "" } else { "No matches" });
+                return;
+            }
+        };
+
+        textViews[searchMatch.textViewIndex].highlightCurrentSearchMatch(
+            LineNumber(searchMatch.line), &searchMatch.charRange);
+        self.currentMatchIndex = Some(index);
+        self.statusLabel.set_text(&format!("{} of {}", index + 1, self.matches.len()));
+    }
+}
+
+// Empty matches of regular expressions like "a*" are skipped, as there would be nothing to highlight.
+fn findMatchesInLine(regex: &Regex, line: &str) -> Vec<CharRange>
+{
+    regex.find_iter(line)
+        .filter(|found| !found.is_empty())
+        .map(|found| {
+            let start = line[..found.start()].chars().count();
+            start..(start + found.as_str().chars().count())
+        })
+        .collect()
+}
+
+fn setupSearchBar(guiElementProvider: &GuiElementProvider, sender: Sender)
+{
+    let searchBar = guiElementProvider.get::<gtk::SearchBar>("commitDiffSearchBar");
+    let entry = guiElementProvider.get::<gtk::SearchEntry>("commitDiffSearchEntry");
+    let caseSensitiveButton = guiElementProvider.get::<gtk::CheckButton>("commitDiffSearchCaseSensitiveCheckButton");
+    let regexButton = guiElementProvider.get::<gtk::CheckButton>("commitDiffSearchRegexCheckButton");
+    searchBar.connect_entry(&entry);
+    let entryClone = entry.clone();
+    // clearing the entry also clears highlighted matches
+    searchBar.connect_search_mode_enabled_notify(move |searchBar| {
+        if !searchBar.is_search_mode() {
+            entryClone.set_text("");
+        }
+    });
+
+    let diffBox = guiElementProvider.get::<gtk::Box>("commitDiffBox");
+    let searchBarClone = searchBar.clone();
+    let entryClone = entry.clone();
+    diffBox.connect_key_press_event(move |_widget, event| {
+        let isFindShortcut = event.state().contains(gdk::ModifierType::CONTROL_MASK)
+            && event.keyval().to_lower() == gdk::keys::constants::f;
+        if !isFindShortcut {
+            return glib::Propagation::Proceed;
+        }
+        searchBarClone.set_search_mode(true);
+        entryClone.grab_focus();
+        glib::Propagation::Stop
+    });
+
+    let sendQuery = {
+        let entry = entry.clone();
+        let caseSensitiveButton = caseSensitiveButton.clone();
+        let regexButton = regexButton.clone();
+        let sender = sender.clone();
+        move || {
+            let query = DiffSearchQuery{
+                text: entry.text().into(),
+                isCaseSensitive: caseSensitiveButton.is_active(),
+                isRegex: regexButton.is_active()
+            };
+            sender.send((Source::CommitDiffSearchBar, Event::DiffSearchChanged(query))).unwrap();
+        }
+    };
+    let sendQueryClone = sendQuery.clone();
+    entry.connect_search_changed(move |_entry| sendQueryClone());
+    let sendQueryClone = sendQuery.clone();
+    caseSensitiveButton.connect_toggled(move |_button| sendQueryClone());
+    regexButton.connect_toggled(move |_button| sendQuery());
+
+    let senderClone = sender.clone();
+    entry.connect_activate(move |_entry| {
+        senderClone.send((Source::CommitDiffSearchBar, Event::DiffSearchNextRequested)).unwrap();
+    });
+    let senderClone = sender.clone();
+    entry.connect_next_match(move |_entry| {
+        senderClone.send((Source::CommitDiffSearchBar, Event::DiffSearchNextRequested)).unwrap();
+    });
+    let senderClone = sender.clone();
+    entry.connect_previous_match(move |_entry| {
+        senderClone.send((Source::CommitDiffSearchBar, Event::DiffSearchPreviousRequested)).unwrap();
+    });
+    let senderClone = sender.clone();
+    guiElementProvider.get::<gtk::Button>("commitDiffSearchNextButton").connect_clicked(move |_button| {
+        senderClone.send((Source::CommitDiffSearchBar, Event::DiffSearchNextRequested)).unwrap();
+    });
+    guiElementProvider.get::<gtk::Button>("commitDiffSearchPreviousButton").connect_clicked(move |_button| {
+        sender.send((Source::CommitDiffSearchBar, Event::DiffSearchPreviousRequested)).unwrap();
+    });
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_CONTEXT_LINES: u32 = 3;
+
+
+// libgit2 implements only these two, the histogram algorithm of git is not available.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum DiffAlgorithm
+{
+    #[default]
+    Myers,
+    Patience
+}
+
+impl DiffAlgorithm
+{
+    pub const ALL: [DiffAlgorithm; 2] = [Self::Myers, Self::Patience];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Myers    => "myers",
+            Self::Patience => "patience"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Myers    => "Myers (default)",
+            Self::Patience => "Patience"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|algorithm| algorithm.getId() == id)
+    }
+}
+
+// Options of diffs shown in the diff view and written to reports.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct DiffSettings
+{
+    pub contextLines: u32,
+    pub ignoreWhitespace: bool,
+    pub ignoreWhitespaceAtEol: bool,
+    pub ignoreBlankLines: bool,
+    pub algorithm: DiffAlgorithm,
+    pub isMinimal: bool
+}
+
+impl Default for DiffSettings
+{
+    fn default() -> Self
+    {
+        Self{
+            contextLines: DEFAULT_CONTEXT_LINES,
+            ignoreWhitespace: false,
+            ignoreWhitespaceAtEol: false,
+            ignoreBlankLines: false,
+            algorithm: DiffAlgorithm::default(),
+            isMinimal: false
+        }
+    }
+}
+
+impl DiffSettings
+{
+    pub fn makeDiffOptions(&self) -> git2::DiffOptions
+    {
+        let mut diffOptions = git2::DiffOptions::new();
+        diffOptions
+            .indent_heuristic(true)
+            .context_lines(self.contextLines)
+            .ignore_whitespace(self.ignoreWhitespace)
+            .ignore_whitespace_eol(self.ignoreWhitespaceAtEol)
+            .ignore_blank_lines(self.ignoreBlankLines)
+            .patience(self.algorithm == DiffAlgorithm::Patience)
+            .minimal(self.isMinimal);
+        diffOptions
+    }
+}
@@ -12,6 +12,9 @@ use crate::options_dialog::OptionsDialog;
 use crate::output_path_label::OutputPathLabel;
 use crate::output_path_store::OutputPathStore;
 use crate::report_generator::ReportGenerator;
+use crate::report_password_dialog::ReportPasswordDialog;
+use crate::report_preview_dialog::ReportPreviewDialog;
+use crate::report_verification_dialog::ReportVerificationDialog;
 use crate::repository_path_label::RepositoryPathLabel;
 use crate::repository_store::RepositoryStore;
 use crate::source::Source;
@@ -36,35 +39,73 @@ pub fn setupDispatching(handlers: EventHandlers, receiver: Receiver)
     let mut repositoryStore = handlers.repositoryStore;
     let mut repositoryPathLabel = handlers.repositoryPathLabel;
     let mut reportGenerator = handlers.reportGenerator;
+    let mut reportPasswordDialog = handlers.reportPasswordDialog;
+    let mut reportPreviewDialog = handlers.reportPreviewDialog;
+    let mut reportVerificationDialog = handlers.reportVerificationDialog;
 
     use Source as S;
     use Event as E;
     attach(receiver, move |(source, event)| { match (source, &event) {
-        (S::ApplicationWindow,                  E::WindowMaximized(_))               => configStore.handle(source, &event),
-        (S::ChooseOutputFolderButtonWidget,     E::Clicked)                          => chooseOutputFolderButton.handle(source, &event),
-        (S::ChooseOutputFolderButton,           E::FolderChosen(_))                  => outputPathStore.handle(source, &event),
-        (S::ChooseOutputFolderDialog,           E::DialogResponded(_))               => chooseOutputFolderButton.handle(source, &event),
-        (S::ChooseRepositoryFolderButton,       E::FolderChosen(_))                  => repositoryStore.handle(source, &event),
-        (S::ChooseRepositoryFolderButtonWidget, E::Clicked)                          => chooseRepositoryFolderButton.handle(source, &event),
-        (S::ChooseRepositoryFolderDialog,       E::DialogResponded(_))               => chooseRepositoryFolderButton.handle(source, &event),
-        (S::CommitDiffViewWidget,               E::ZoomRequested(_))                 => commitDiffView.handle(source, &event),
-        (S::CommitAuthorFilterEntry,            E::CommitAuthorFilterChanged(_))     => (&mut commitLogModelFilter, &mut configStore).handle(source, &event),
-        (S::CommitLog,                          E::CommitLogChanged)                 => commitLogModel.handle(source, &event),
-        (S::CommitLogModelFilter,               E::MarkCommitForReportToggled(_))    => commitLogModel.handle(source, &event),
-        (S::CommitLogView,                      E::CommitSelected(_))                => commitDiffView.handle(source, &event),
-        (S::CommitLogView,                      E::CommitUnselected)                 => commitDiffView.handle(source, &event),
-        (S::CommitLogViewCheckButton,           E::MarkCommitForReportToggled(_))    => commitLogModelFilter.handle(source, &event),
-        (S::CommitLogViewWidget,                E::SelectionChanged(_))              => commitLogView.handle(source, &event),
-        (S::GenerateReportButton,               E::GenerateReportRequested)          => reportGenerator.handle(source, &event),
-        (S::MonthComboBox,                      E::MonthFilterChanged(_))            => (&mut commitLogModelFilter, &mut outputPathStore).handle(source, &event),
-        (S::OpenOptionsButton,                  E::OpenOptionsRequested)             => optionsDialog.handle(source, &event),
-        (S::OptionsDialog,                      E::OutputFileNamesPatternChanged(_)) => reportGenerator.handle(source, &event),
-        (S::OptionsDialogWidget,                E::DialogResponded(_))               => optionsDialog.handle(source, &event),
-        (S::OutputPathStore,                    E::OutputPathChanged(_))             => (&mut outputPathLabel, &mut reportGenerator, &mut configStore).handle(source, &event),
-        (S::OutputPathStore,                    E::PartialOutputPathChanged(_))      => outputPathLabel.handle(source, &event),
-        (S::PaneWithCommitLogAndDiff,           E::PanePositionChanged(_))           => configStore.handle(source, &event),
-        (S::RepositoryStore,                    E::RepositoryChanged(_))             => (&mut repositoryPathLabel, &mut commitLog, &mut commitDiffView, &mut reportGenerator, &mut configStore).handle(source, &event),
-        (S::YearSpinButton,                     E::YearFilterChanged(_))             => (&mut commitLogModelFilter, &mut outputPathStore).handle(source, &event),
+        (S::ApplicationWindow,                  E::WindowMaximized(_))                  => configStore.handle(source, &event),
+        (S::ChooseOutputFolderButtonWidget,     E::Clicked)                             => chooseOutputFolderButton.handle(source, &event),
+        (S::ChooseOutputFolderButton,           E::FolderChosen(_))                     => outputPathStore.handle(source, &event),
+        (S::ChooseOutputFolderDialog,           E::DialogResponded(_))                  => chooseOutputFolderButton.handle(source, &event),
+        (S::ChooseRepositoryFolderButton,       E::FolderChosen(_))                     => repositoryStore.handle(source, &event),
+        (S::ChooseRepositoryFolderButtonWidget, E::Clicked)                             => chooseRepositoryFolderButton.handle(source, &event),
+        (S::ChooseRepositoryFolderDialog,       E::DialogResponded(_))                  => chooseRepositoryFolderButton.handle(source, &event),
+        (S::CommitDiffFilesViewCheckButton,     E::DiffFileSectionToggled(_))           => commitDiffView.handle(source, &event),
+        (S::CommitDiffFilesViewWidget,          E::SelectionChanged(_))                 => commitDiffView.handle(source, &event),
+        (S::CommitDiffSearchBar,                E::DiffSearchChanged(_))                => commitDiffView.handle(source, &event),
+        (S::CommitDiffSearchBar,                E::DiffSearchNextRequested)             => commitDiffView.handle(source, &event),
+        (S::CommitDiffSearchBar,                E::DiffSearchPreviousRequested)         => commitDiffView.handle(source, &event),
+        (S::CommitDiffView,                     E::DiffFontSizeChanged(_))              => configStore.handle(source, &event),
+        (S::CommitDiffViewWidget,               E::ThemeChanged)                        => commitDiffView.handle(source, &event),
+        (S::CommitDiffViewWidget,               E::ZoomRequested(_))                    => commitDiffView.handle(source, &event),
+        (S::CommitAuthorFilterEntry,            E::CommitAuthorFilterChanged(_))        => (&mut commitLogModelFilter, &mut commitLogView, &mut configStore).handle(source, &event),
+        (S::CommitLog,                          E::CommitLogChanged)                    => commitLogModel.handle(source, &event),
+        (S::CommitLogModel,                     E::CommitAnnotationChanged(_, _))       => configStore.handle(source, &event),
+        (S::CommitLogModelFilter,               E::CommitCreativeWorkEdited(_, _))      => commitLogModel.handle(source, &event),
+        (S::CommitLogModelFilter,               E::CommitDescriptionEdited(_, _))       => commitLogModel.handle(source, &event),
+        (S::CommitLogModelFilter,               E::CommitHoursEdited(_, _))             => commitLogModel.handle(source, &event),
+        (S::CommitLogModelFilter,               E::MarkCommitForReportToggled(_))       => commitLogModel.handle(source, &event),
+        (S::CommitLogModelFilter,               E::MarkCommitsForReportRequested(_, _)) => commitLogModel.handle(source, &event),
+        (S::CommitLogView,                      E::CommitDiffStatsRequested)            => commitLog.handle(source, &event),
+        (S::CommitLogView,                      E::CommitSelected(_))                   => commitDiffView.handle(source, &event),
+        (S::CommitLogView,                      E::CommitUnselected)                    => commitDiffView.handle(source, &event),
+        (S::CommitLogView,                      E::MarkCommitsForReportRequested(_, _)) => commitLogModelFilter.handle(source, &event),
+        (S::CommitLogViewCheckButton,           E::MarkCommitForReportToggled(_))       => commitLogModelFilter.handle(source, &event),
+        (S::CommitLogViewCreativeWorkCell,      E::CommitCreativeWorkEdited(_, _))      => commitLogModelFilter.handle(source, &event),
+        (S::CommitLogViewDescriptionCell,       E::CommitDescriptionEdited(_, _))       => commitLogModelFilter.handle(source, &event),
+        (S::CommitLogViewHoursCell,             E::CommitHoursEdited(_, _))             => commitLogModelFilter.handle(source, &event),
+        (S::CommitLogViewMenu,                  E::BulkMarkingRequested(_))             => commitLogView.handle(source, &event),
+        (S::CommitLogViewWidget,                E::BulkMarkingRequested(_))             => commitLogView.handle(source, &event),
+        (S::CommitLogViewWidget,                E::CommitLogColumnsChanged(_))          => (&mut commitLogView, &mut configStore).handle(source, &event),
+        (S::CommitLogViewWidget,                E::SelectionChanged(_))                 => commitLogView.handle(source, &event),
+        (S::GenerateReportButton,               E::GenerateReportRequested)             => reportGenerator.handle(source, &event),
+        (S::MonthComboBox,                      E::MonthFilterChanged(_))               => (&mut commitLog, &mut commitLogModelFilter, &mut outputPathStore).handle(source, &event),
+        (S::OpenOptionsButton,                  E::OpenOptionsRequested)                => optionsDialog.handle(source, &event),
+        (S::OptionsDialog,                      E::AutoMarkingRulesChanged(_))          => (&mut commitLog, &mut configStore).handle(source, &event),
+        (S::OptionsDialog,                      E::DateFormatChanged(_))                => (&mut commitLogModel, &mut commitDiffView, &mut reportGenerator, &mut configStore).handle(source, &event),
+        (S::OptionsDialog,                      E::DiffColorsChanged(_))                => (&mut commitDiffView, &mut configStore).handle(source, &event),
+        (S::OptionsDialog,                      E::DiffSettingsChanged(_))              => (&mut commitLog, &mut commitDiffView, &mut reportGenerator, &mut configStore).handle(source, &event),
+        (S::OptionsDialog,                      E::OutputFileNamesPatternChanged(_))    => reportGenerator.handle(source, &event),
+        (S::OptionsDialog,                      E::ReportSettingsChanged(_))            => (&mut reportGenerator, &mut configStore).handle(source, &event),
+        (S::OptionsDialog,                      E::TextNormalizationSettingsChanged(_)) => (&mut reportGenerator, &mut configStore).handle(source, &event),
+        (S::OptionsDialogWidget,                E::DialogResponded(_))                  => optionsDialog.handle(source, &event),
+        (S::OutputPathStore,                    E::OutputPathChanged(_))                => (&mut outputPathLabel, &mut reportGenerator, &mut reportVerificationDialog, &mut configStore).handle(source, &event),
+        (S::OutputPathStore,                    E::PartialOutputPathChanged(_))         => outputPathLabel.handle(source, &event),
+        (S::PaneWithCommitLogAndDiff,           E::PanePositionChanged(_))              => configStore.handle(source, &event),
+        (S::ReportGenerator,                    E::ReportGenerationFailed(_))           => reportPreviewDialog.handle(source, &event),
+        (S::ReportGenerator,                    E::ReportPasswordRequested)             => reportPasswordDialog.handle(source, &event),
+        (S::ReportGenerator,                    E::ReportPreviewPrepared(_))            => reportPreviewDialog.handle(source, &event),
+        (S::ReportPasswordDialog,               E::ReportPasswordEntered(_))            => reportGenerator.handle(source, &event),
+        (S::ReportPasswordDialogWidget,         E::DialogResponded(_))                  => reportPasswordDialog.handle(source, &event),
+        (S::ReportPreviewDialog,                E::ReportGenerationConfirmed)           => reportGenerator.handle(source, &event),
+        (S::ReportPreviewDialogWidget,          E::DialogResponded(_))                  => reportPreviewDialog.handle(source, &event),
+        (S::RepositoryStore,                    E::RepositoryChanged(_))                => (&mut repositoryPathLabel, &mut commitLog, &mut commitDiffView, &mut reportGenerator, &mut reportVerificationDialog, &mut optionsDialog, &mut configStore).handle(source, &event),
+        (S::SideBySideDiffToggleButton,         E::SideBySideDiffToggled(_))            => (&mut commitDiffView, &mut configStore).handle(source, &event),
+        (S::VerifyReportsButton,                E::VerifyReportsRequested)              => reportVerificationDialog.handle(source, &event),
+        (S::YearSpinButton,                     E::YearFilterChanged(_))                => (&mut commitLog, &mut commitLogModelFilter, &mut outputPathStore).handle(source, &event),
         (source, event) => onUnknown(source, event) }
 
         glib::ControlFlow::Continue
@@ -85,6 +126,9 @@ pub struct EventHandlers
     pub outputPathLabel: OutputPathLabel,
     pub outputPathStore: OutputPathStore,
     pub reportGenerator: ReportGenerator,
+    pub reportPasswordDialog: ReportPasswordDialog,
+    pub reportPreviewDialog: ReportPreviewDialog,
+    pub reportVerificationDialog: ReportVerificationDialog,
     pub repositoryStore: RepositoryStore,
     pub repositoryPathLabel: RepositoryPathLabel
 }
@@ -135,6 +179,36 @@ impl<T0, T1, T2, T3, T4> EventHandler for (T0, T1, T2, T3, T4)
     }
 }
 
+impl<T0, T1, T2, T3, T4, T5> EventHandler for (T0, T1, T2, T3, T4, T5)
+    where T0: EventHandler, T1: EventHandler, T2: EventHandler, T3: EventHandler, T4: EventHandler, T5: EventHandler
+{
+    fn handle(&mut self, source: Source, event: &Event)
+    {
+        self.0.handle(source, event);
+        self.1.handle(source, event);
+        self.2.handle(source, event);
+        self.3.handle(source, event);
+        self.4.handle(source, event);
+        self.5.handle(source, event);
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6> EventHandler for (T0, T1, T2, T3, T4, T5, T6)
+    where T0: EventHandler, T1: EventHandler, T2: EventHandler, T3: EventHandler, T4: EventHandler, T5: EventHandler,
+          T6: EventHandler
+{
+    fn handle(&mut self, source: Source, event: &Event)
+    {
+        self.0.handle(source, event);
+        self.1.handle(source, event);
+        self.2.handle(source, event);
+        self.3.handle(source, event);
+        self.4.handle(source, event);
+        self.5.handle(source, event);
+        self.6.handle(source, event);
+    }
+}
+
 impl<T> EventHandler for &mut T
     where T: EventHandler
 {
@@ -0,0 +1,18 @@
+use gtk::glib;
+use gtk::prelude::DialogExt as _;
+use gtk::prelude::GtkWindowExt as _;
+use gtk::prelude::WidgetExt as _;
+
+
+// Shows an error, which the user only has to acknowledge, so the dialog holds no state.
+pub fn showErrorDialog(parentWindow: &impl glib::IsA<gtk::Window>, text: &str)
+{
+    let dialog = gtk::MessageDialog::new(
+        Some(parentWindow),
+        gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+        gtk::MessageType::Error,
+        gtk::ButtonsType::Close,
+        text);
+    dialog.connect_response(|dialog, _response| dialog.close());
+    dialog.show_all();
+}
@@ -1,7 +1,18 @@
+use crate::auto_marking::AutoMarkingRules;
+use crate::commit_annotation::CommitAnnotation;
+use crate::commit_log_columns::CommitLogColumnsSettings;
+use crate::date_format::DateFormat;
+use crate::diff_colors::DiffColorSettings;
+use crate::diff_search::DiffSearchQuery;
+use crate::diff_settings::DiffSettings;
 use crate::pane_with_commit_log_and_diff::PanePosition;
+use crate::report_archive::ArchivePassword;
+use crate::report_preview::ReportPreview;
+use crate::report_settings::ReportSettings;
 use crate::repository::Repository;
+use crate::text_normalization::TextNormalizationSettings;
+use crate::text_view::FontSize;
 
-use gtk::gdk;
 use std::path::PathBuf;
 use std::rc::Rc;
 use time::Month;
@@ -10,26 +21,82 @@ use time::Month;
 #[derive(Debug)]
 pub enum Event
 {
+    AutoMarkingRulesChanged(AutoMarkingRules),
+    BulkMarkingRequested(BulkMarking),
     Clicked,
+    CommitAnnotationChanged(git2::Oid, CommitAnnotation),
     CommitAuthorFilterChanged(CommitAuthorFilter),
+    CommitCreativeWorkEdited(gtk::TreePath, String),
+    CommitDescriptionEdited(gtk::TreePath, String),
+    CommitDiffStatsRequested,
+    CommitHoursEdited(gtk::TreePath, String),
     CommitLogChanged,
+    CommitLogColumnsChanged(CommitLogColumnsSettings),
     CommitSelected(git2::Oid),
     CommitUnselected,
+    DateFormatChanged(DateFormat),
     DialogResponded(gtk::ResponseType),
+    DiffColorsChanged(Box<DiffColorSettings>),
+    DiffFileSectionToggled(gtk::TreePath),
+    DiffFontSizeChanged(Option<FontSize>),
+    DiffSearchChanged(DiffSearchQuery),
+    DiffSearchNextRequested,
+    DiffSearchPreviousRequested,
+    DiffSettingsChanged(DiffSettings),
     FolderChosen(PathBuf),
     GenerateReportRequested,
     MarkCommitForReportToggled(gtk::TreePath),
+    MarkCommitsForReportRequested(Vec<gtk::TreePath>, Marking),
     MonthFilterChanged(Month),
     OpenOptionsRequested,
     OutputFileNamesPatternChanged(String),
     OutputPathChanged(OutputPathInfo),
     PartialOutputPathChanged(PathBuf),
     PanePositionChanged(PanePosition),
+    ReportGenerationConfirmed,
+    ReportGenerationFailed(String),
+    ReportPasswordEntered(ArchivePassword),
+    ReportPasswordRequested,
+    ReportPreviewPrepared(ReportPreview),
+    ReportSettingsChanged(ReportSettings),
     RepositoryChanged(Rc<Repository>),
     SelectionChanged(gtk::TreeSelection),
+    SideBySideDiffToggled(bool),
+    TextNormalizationSettingsChanged(TextNormalizationSettings),
+    ThemeChanged,
+    VerifyReportsRequested,
     WindowMaximized(bool),
     YearFilterChanged(Year),
-    ZoomRequested(gdk::EventScroll)
+    ZoomRequested(Zoom)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BulkMarking
+{
+    MarkAll,
+    UnmarkAll,
+    InvertAll,
+    MarkSelected,
+    UnmarkSelected,
+    ToggleSelected,
+    MarkAllBySelectedAuthors,
+    MarkAllByFilteredAuthor
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Marking
+{
+    Mark,
+    Unmark,
+    Toggle
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Zoom
+{
+    In,
+    Out,
+    Reset
 }
 
 pub type CommitAuthorFilter = String;
@@ -21,8 +21,12 @@ use crate::output_path_label::OutputPathLabel;
 use crate::output_path_store::OutputPathStore;
 use crate::pane_with_commit_log_and_diff::setupPaneWithCommitLogAndCommitDiff;
 use crate::report_generator::ReportGenerator;
+use crate::report_password_dialog::ReportPasswordDialog;
+use crate::report_preview_dialog::ReportPreviewDialog;
+use crate::report_verification_dialog::ReportVerificationDialog;
 use crate::repository_path_label::RepositoryPathLabel;
 use crate::repository_store::RepositoryStore;
+use crate::verify_reports_button::setupVerifyReportsButton;
 use crate::year_filter_spin_button::setupYearFilterSpinButton;
 
 use std::cell::RefCell;
@@ -49,22 +53,35 @@ impl Gui
         let applicationWindow = ApplicationWindow::new(config, &guiElementProvider, sender.clone());
         let chooseOutputFolderButton = makeChooseOutputFolderButton(&guiElementProvider, sender.clone());
         let chooseRepositoryFolderButton = makeChooseRepositoryFolderButton(&guiElementProvider, sender.clone());
-        let optionsDialog = OptionsDialog::new(outputFileNamesPattern, sender.clone());
+        let optionsDialog = OptionsDialog::new(config, outputFileNamesPattern, sender.clone());
         let outputPathLabel = OutputPathLabel::new(config, currentDate, &guiElementProvider);
         let outputPathStore = OutputPathStore::new(config, currentDate, sender.clone());
         let repositoryStore = RepositoryStore::new(config, sender.clone());
         let repository = repositoryStore.getRepository();
         let repositoryPathLabel = RepositoryPathLabel::new(repositoryStore.getRepositoryPath(), &guiElementProvider);
-        let commitLog = Rc::new(RefCell::new(CommitLog::new(repository, sender.clone())));
+        let commitLog = Rc::new(RefCell::new(CommitLog::new(
+            config, repository, currentDate, &guiElementProvider, sender.clone())));
         let commitLogModelFilter = CommitLogModelFilter::new(
             config, Rc::clone(&commitLog), &guiElementProvider, sender.clone());
-        let commitLogModel = CommitLogModel::new(Rc::clone(&commitLog), &guiElementProvider);
-        let commitLogView = CommitLogView::new(Rc::clone(&commitLog), &guiElementProvider, sender.clone());
-        let commitDiffView = CommitDiffView::new(repository.clone(), &guiElementProvider, sender.clone());
+        let commitLogModel = CommitLogModel::new(
+            config.dateFormat, Rc::clone(&commitLog), &guiElementProvider, sender.clone());
+        let commitLogView = CommitLogView::new(
+            config, Rc::clone(&commitLog), &guiElementProvider, sender.clone());
+        let commitDiffView = CommitDiffView::new(
+            config, repository.clone(), &guiElementProvider, sender.clone());
         let reportGenerator = ReportGenerator::new(
-            Rc::clone(&commitLog), repository.clone(),  outputPathStore.getPath().clone(), outputFileNamesPattern);
+            config,
+            Rc::clone(&commitLog),
+            repository.clone(),
+            outputPathStore.getPath().clone(),
+            outputFileNamesPattern,
+            sender.clone());
+        let reportPasswordDialog = ReportPasswordDialog::new(&guiElementProvider, sender.clone());
+        let reportPreviewDialog = ReportPreviewDialog::new(&guiElementProvider, sender.clone());
+        let reportVerificationDialog = ReportVerificationDialog::new(config, repository.clone(), &guiElementProvider);
         setupOpenOptionsButton(&guiElementProvider, sender.clone());
         setupGenerateReportButton(&guiElementProvider, sender.clone());
+        setupVerifyReportsButton(&guiElementProvider, sender.clone());
         setupCommitAuthorFilterEntry(config, &guiElementProvider, sender.clone());
         setupMonthFilterComboBox(&currentDate, &guiElementProvider, sender.clone());
         setupYearFilterSpinButton(&currentDate, &guiElementProvider, sender.clone());
@@ -83,6 +100,9 @@ impl Gui
             outputPathLabel,
             outputPathStore,
             reportGenerator,
+            reportPasswordDialog,
+            reportPreviewDialog,
+            reportVerificationDialog,
             repositoryStore,
             repositoryPathLabel
         };
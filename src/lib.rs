@@ -1,27 +1,39 @@
 #![allow(non_snake_case)]
 #![allow(clippy::new_without_default)]
 
+pub mod command_line;
 pub mod config_path;
 pub mod date_time;
 pub mod gui;
 
 mod application_window;
+mod auto_marking;
 mod channel;
 mod choose_folder_button;
 mod choose_output_folder_button;
 mod choose_repository_folder_button;
+mod commit_annotation;
 mod commit_author_filter_entry;
 mod commit_diff;
 mod commit_diff_view;
 mod commit_log;
 mod commit_log_column;
+mod commit_log_columns;
 mod commit_log_model;
 mod commit_log_model_filter;
 mod commit_log_view;
+mod commit_log_view_menu;
+mod commit_trailers;
 mod config_store;
+mod date_format;
 mod diff_colorizer;
+mod diff_colors;
+mod diff_files_view;
 mod diff_formatter;
+mod diff_search;
+mod diff_settings;
 mod dispatcher;
+mod error_dialog;
 mod event;
 mod event_handling;
 mod generate_report_button;
@@ -33,12 +45,27 @@ mod options_dialog;
 mod output_path_label;
 mod output_path_store;
 mod pane_with_commit_log_and_diff;
+mod report_archive;
 mod report_generator;
+mod report_manifest;
+mod report_password_dialog;
+mod report_preview;
+mod report_preview_dialog;
+mod report_settings;
+mod report_summary;
+mod report_verification;
+mod report_verification_dialog;
 mod repository;
 mod repository_path_label;
 mod repository_store;
+mod side_by_side_diff;
+mod side_by_side_diff_view;
 mod source;
+mod syntax_highlighter;
+mod text_normalization;
 mod text_view;
 mod tree_view;
 mod tree_view_column_config;
+mod verify_reports_button;
+mod word_diff;
 mod year_filter_spin_button;
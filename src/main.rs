@@ -1,15 +1,24 @@
 #![allow(non_snake_case)]
 
+use rusty_tax_break::command_line::runCommand;
 use rusty_tax_break::config_path::ConfigPath;
 use rusty_tax_break::gui::Gui;
 
 use gtk::glib;
+use std::process::ExitCode;
 
-fn main()
+fn main() -> ExitCode
 {
+    let configPath = ConfigPath::default();
+    let args = std::env::args_os().skip(1).collect::<Vec<_>>();
+    if let Some(exitCode) = runCommand(&args, &configPath) {
+        return exitCode;
+    }
+
     let context = glib::MainContext::default();
     let _guard = context.acquire().unwrap();
-    let gui = Gui::new(&ConfigPath::default());
+    let gui = Gui::new(&configPath);
     gui.show();
     gui.run();
+    ExitCode::SUCCESS
 }
@@ -1,18 +1,47 @@
+use crate::auto_marking::{AutoMarkingRules, formatAutoMarkingRules, parseAutoMarkingRules};
+use crate::config_store::Config;
+use crate::date_format::DateFormat;
+use crate::diff_colors::{DiffColorSettings, DiffColorTheme, DiffHighlighting};
+use crate::diff_settings::{DiffAlgorithm, DiffSettings};
 use crate::event::Event;
 use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
+use crate::report_archive::ArchiveFormat;
+use crate::report_settings::{ArchiveComposition, normalizeReportNames, ReportSettings};
+use crate::repository::Repository;
 use crate::source::Source;
+use crate::text_normalization::{LineEndings, SourceEncoding, TextNormalizationSettings};
 
 use gtk::prelude::ButtonExt as _;
+use gtk::prelude::ComboBoxExt as _;
+use gtk::prelude::ComboBoxTextExt as _;
 use gtk::prelude::DialogExt as _;
 use gtk::prelude::EntryExt as _;
 use gtk::prelude::GtkWindowExt as _;
+use gtk::prelude::LabelExt as _;
+use gtk::prelude::SpinButtonExt as _;
+use gtk::prelude::TextBufferExt as _;
+use gtk::prelude::TextViewExt as _;
+use gtk::prelude::ToggleButtonExt as _;
 use gtk::prelude::WidgetExt as _;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const INCLUDE_HIDDEN_CHARS: bool = true;
 
 
 pub struct OptionsDialog
 {
     outputFileNamesPattern: String,
+    autoMarkingRules: AutoMarkingRules,
+    dateFormat: DateFormat,
+    diffColors: DiffColorSettings,
+    diffSettings: DiffSettings,
+    reportSettings: ReportSettings,
+    // only settings of the current repository are edited, the others are kept for when it changes back
+    textNormalization: BTreeMap<PathBuf, TextNormalizationSettings>,
+    repositoryPath: Option<PathBuf>,
     widgets: Option<Widgets>,
     sender: Sender
 }
@@ -24,6 +53,7 @@ impl EventHandler for OptionsDialog
         match event {
             Event::DialogResponded(response) => self.onDialogResponded(*response),
             Event::OpenOptionsRequested      => self.open(),
+            Event::RepositoryChanged(repo)   => self.onRepositoryChanged(repo),
             _ => onUnknown(source, event)
         }
     }
@@ -31,9 +61,20 @@ impl EventHandler for OptionsDialog
 
 impl OptionsDialog
 {
-    pub fn new(outputFileNamesPattern: &str, sender: Sender) -> Self
+    pub fn new(config: &Config, outputFileNamesPattern: &str, sender: Sender) -> Self
     {
-        Self{outputFileNamesPattern: outputFileNamesPattern.into(), widgets: None, sender}
+        Self{
+            outputFileNamesPattern: outputFileNamesPattern.into(),
+            autoMarkingRules: config.autoMarkingRules.clone(),
+            dateFormat: config.dateFormat,
+            diffColors: config.diffColors.clone(),
+            diffSettings: config.diffSettings.clone(),
+            reportSettings: config.reportSettings.clone(),
+            textNormalization: config.textNormalization.clone(),
+            repositoryPath: config.repository.clone(),
+            widgets: None,
+            sender
+        }
     }
 
     fn open(&mut self)
@@ -41,6 +82,28 @@ impl OptionsDialog
         let guiElementProvider = GuiElementProvider::new(include_str!("options_dialog.glade"));
         let patternEntry = guiElementProvider.get::<gtk::Entry>("outputFileNamesPatternEntry");
         patternEntry.set_text(&self.outputFileNamesPattern);
+        let rulesTextView = guiElementProvider.get::<gtk::TextView>("autoMarkingRulesTextView");
+        rulesTextView.buffer().unwrap().set_text(&formatAutoMarkingRules(&self.autoMarkingRules));
+        let rulesErrorLabel = guiElementProvider.get::<gtk::Label>("autoMarkingRulesErrorLabel");
+        let dateFormatComboBox = guiElementProvider.get::<gtk::ComboBoxText>("dateFormatComboBox");
+        for dateFormat in DateFormat::ALL {
+            dateFormatComboBox.append(Some(dateFormat.getId()), dateFormat.getLabel());
+        }
+        dateFormatComboBox.set_active_id(Some(self.dateFormat.getId()));
+        let diffColorThemeComboBox = guiElementProvider.get::<gtk::ComboBoxText>("diffColorThemeComboBox");
+        for theme in DiffColorTheme::ALL {
+            diffColorThemeComboBox.append(Some(theme.getId()), theme.getLabel());
+        }
+        diffColorThemeComboBox.set_active_id(Some(self.diffColors.theme.getId()));
+        let diffHighlightingComboBox = guiElementProvider.get::<gtk::ComboBoxText>("diffHighlightingComboBox");
+        for highlighting in DiffHighlighting::ALL {
+            diffHighlightingComboBox.append(Some(highlighting.getId()), highlighting.getLabel());
+        }
+        diffHighlightingComboBox.set_active_id(Some(self.diffColors.highlighting.getId()));
+        let diffSettingsWidgets = DiffSettingsWidgets::new(&guiElementProvider, &self.diffSettings);
+        let reportSettingsWidgets = ReportSettingsWidgets::new(&guiElementProvider, &self.reportSettings);
+        let textNormalizationWidgets =
+            TextNormalizationWidgets::new(&guiElementProvider, &self.getTextNormalizationSettings());
 
         let dialog = guiElementProvider.get::<gtk::Dialog>("dialog");
         let sender = self.sender.clone();
@@ -63,7 +126,18 @@ impl OptionsDialog
         dialog.set_modal(true);
         dialog.show();
 
-        self.widgets = Some(Widgets{dialog, outputFileNamesPatternEntry: patternEntry});
+        self.widgets = Some(Widgets{
+            dialog,
+            outputFileNamesPatternEntry: patternEntry,
+            autoMarkingRulesTextView: rulesTextView,
+            autoMarkingRulesErrorLabel: rulesErrorLabel,
+            dateFormatComboBox,
+            diffColorThemeComboBox,
+            diffHighlightingComboBox,
+            diffSettingsWidgets,
+            reportSettingsWidgets,
+            textNormalizationWidgets
+        });
     }
 
     fn onDialogResponded(&mut self, response: gtk::ResponseType)
@@ -86,14 +160,92 @@ impl OptionsDialog
             }
         };
 
+        // nothing is applied until all settings are valid, so that the dialog never stays open half saved
+        let rulesBuffer = widgets.autoMarkingRulesTextView.buffer().unwrap();
+        let rulesText = rulesBuffer.text(&rulesBuffer.start_iter(), &rulesBuffer.end_iter(), INCLUDE_HIDDEN_CHARS)
+            .unwrap();
+        let newRulesResult = parseAutoMarkingRules(&rulesText);
+        let newReportSettingsResult = widgets.reportSettingsWidgets.getSettings();
+        showError(&widgets.autoMarkingRulesErrorLabel, &newRulesResult);
+        showError(&widgets.reportSettingsWidgets.namesErrorLabel, &newReportSettingsResult);
+        let (newRules, newReportSettings) = match (newRulesResult, newReportSettingsResult) {
+            (Ok(rules), Ok(reportSettings)) => (rules, reportSettings),
+            _ => return
+        };
+
+        if self.autoMarkingRules != newRules {
+            self.autoMarkingRules = newRules.clone();
+            self.sender.send((Source::OptionsDialog, Event::AutoMarkingRulesChanged(newRules))).unwrap();
+        }
+
         let newPattern = widgets.outputFileNamesPatternEntry.text().to_string();
         if self.outputFileNamesPattern != newPattern {
             self.outputFileNamesPattern = newPattern.clone();
             self.sender.send((Source::OptionsDialog, Event::OutputFileNamesPatternChanged(newPattern))).unwrap();
         }
+
+        let newDateFormat = widgets.dateFormatComboBox.active_id()
+            .and_then(|id| DateFormat::fromId(&id))
+            .unwrap_or_default();
+        if self.dateFormat != newDateFormat {
+            self.dateFormat = newDateFormat;
+            self.sender.send((Source::OptionsDialog, Event::DateFormatChanged(newDateFormat))).unwrap();
+        }
+
+        let newDiffColors = DiffColorSettings{
+            theme: widgets.diffColorThemeComboBox.active_id()
+                .and_then(|id| DiffColorTheme::fromId(&id))
+                .unwrap_or_default(),
+            highlighting: widgets.diffHighlightingComboBox.active_id()
+                .and_then(|id| DiffHighlighting::fromId(&id))
+                .unwrap_or_default(),
+            customColors: self.diffColors.customColors.clone()
+        };
+        if self.diffColors != newDiffColors {
+            self.diffColors = newDiffColors.clone();
+            self.sender.send((Source::OptionsDialog, Event::DiffColorsChanged(Box::new(newDiffColors)))).unwrap();
+        }
+
+        let newDiffSettings = widgets.diffSettingsWidgets.getSettings();
+        if self.diffSettings != newDiffSettings {
+            self.diffSettings = newDiffSettings.clone();
+            self.sender.send((Source::OptionsDialog, Event::DiffSettingsChanged(newDiffSettings))).unwrap();
+        }
+
+        if self.reportSettings != newReportSettings {
+            self.reportSettings = newReportSettings.clone();
+            self.sender.send((Source::OptionsDialog, Event::ReportSettingsChanged(newReportSettings))).unwrap();
+        }
+
+        let newTextNormalization = widgets.textNormalizationWidgets.getSettings();
+        if self.getTextNormalizationSettings() != newTextNormalization {
+            if let Some(repositoryPath) = &self.repositoryPath {
+                self.textNormalization.insert(repositoryPath.clone(), newTextNormalization.clone());
+            }
+            self.sender.send((Source::OptionsDialog, Event::TextNormalizationSettingsChanged(newTextNormalization)))
+                .unwrap();
+        }
         self.close();
     }
 
+    fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
+    {
+        if self.repositoryPath.as_deref() == Some(repo.getPath()) {
+            return;
+        }
+        self.repositoryPath = Some(repo.getPath().into());
+        let settings = self.getTextNormalizationSettings();
+        self.sender.send((Source::OptionsDialog, Event::TextNormalizationSettingsChanged(settings))).unwrap();
+    }
+
+    fn getTextNormalizationSettings(&self) -> TextNormalizationSettings
+    {
+        self.repositoryPath.as_ref()
+            .and_then(|repositoryPath| self.textNormalization.get(repositoryPath))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn onCancelDialog(&mut self)
     {
         self.close();
@@ -122,5 +274,191 @@ impl OptionsDialog
 struct Widgets
 {
     dialog: gtk::Dialog,
-    outputFileNamesPatternEntry: gtk::Entry
+    outputFileNamesPatternEntry: gtk::Entry,
+    autoMarkingRulesTextView: gtk::TextView,
+    autoMarkingRulesErrorLabel: gtk::Label,
+    dateFormatComboBox: gtk::ComboBoxText,
+    diffColorThemeComboBox: gtk::ComboBoxText,
+    diffHighlightingComboBox: gtk::ComboBoxText,
+    diffSettingsWidgets: DiffSettingsWidgets,
+    reportSettingsWidgets: ReportSettingsWidgets,
+    textNormalizationWidgets: TextNormalizationWidgets
+}
+
+struct DiffSettingsWidgets
+{
+    contextLinesSpinButton: gtk::SpinButton,
+    algorithmComboBox: gtk::ComboBoxText,
+    ignoreWhitespaceCheckButton: gtk::CheckButton,
+    ignoreWhitespaceAtEolCheckButton: gtk::CheckButton,
+    ignoreBlankLinesCheckButton: gtk::CheckButton,
+    minimalCheckButton: gtk::CheckButton
+}
+
+impl DiffSettingsWidgets
+{
+    fn new(guiElementProvider: &GuiElementProvider, settings: &DiffSettings) -> Self
+    {
+        let newSelf = Self{
+            contextLinesSpinButton: guiElementProvider.get::<gtk::SpinButton>("diffContextLinesSpinButton"),
+            algorithmComboBox: guiElementProvider.get::<gtk::ComboBoxText>("diffAlgorithmComboBox"),
+            ignoreWhitespaceCheckButton: guiElementProvider.get::<gtk::CheckButton>("diffIgnoreWhitespaceCheckButton"),
+            ignoreWhitespaceAtEolCheckButton:
+                guiElementProvider.get::<gtk::CheckButton>("diffIgnoreWhitespaceAtEolCheckButton"),
+            ignoreBlankLinesCheckButton: guiElementProvider.get::<gtk::CheckButton>("diffIgnoreBlankLinesCheckButton"),
+            minimalCheckButton: guiElementProvider.get::<gtk::CheckButton>("diffMinimalCheckButton")
+        };
+        newSelf.contextLinesSpinButton.set_value(settings.contextLines.into());
+        for algorithm in DiffAlgorithm::ALL {
+            newSelf.algorithmComboBox.append(Some(algorithm.getId()), algorithm.getLabel());
+        }
+        newSelf.algorithmComboBox.set_active_id(Some(settings.algorithm.getId()));
+        newSelf.ignoreWhitespaceCheckButton.set_active(settings.ignoreWhitespace);
+        newSelf.ignoreWhitespaceAtEolCheckButton.set_active(settings.ignoreWhitespaceAtEol);
+        newSelf.ignoreBlankLinesCheckButton.set_active(settings.ignoreBlankLines);
+        newSelf.minimalCheckButton.set_active(settings.isMinimal);
+        newSelf
+    }
+
+    fn getSettings(&self) -> DiffSettings
+    {
+        DiffSettings{
+            contextLines: self.contextLinesSpinButton.value_as_int().try_into().unwrap_or_default(),
+            ignoreWhitespace: self.ignoreWhitespaceCheckButton.is_active(),
+            ignoreWhitespaceAtEol: self.ignoreWhitespaceAtEolCheckButton.is_active(),
+            ignoreBlankLines: self.ignoreBlankLinesCheckButton.is_active(),
+            algorithm: self.algorithmComboBox.active_id()
+                .and_then(|id| DiffAlgorithm::fromId(&id))
+                .unwrap_or_default(),
+            isMinimal: self.minimalCheckButton.is_active()
+        }
+    }
+}
+
+struct ReportSettingsWidgets
+{
+    archiveFormatComboBox: gtk::ComboBoxText,
+    archiveCompositionComboBox: gtk::ComboBoxText,
+    fullFilesFolderNameEntry: gtk::Entry,
+    diffFileNameEntry: gtk::Entry,
+    namesErrorLabel: gtk::Label,
+    noteCoAuthorsCheckButton: gtk::CheckButton,
+    encryptZipArchivesCheckButton: gtk::CheckButton,
+    expandSubmodulesCheckButton: gtk::CheckButton
+}
+
+impl ReportSettingsWidgets
+{
+    fn new(guiElementProvider: &GuiElementProvider, settings: &ReportSettings) -> Self
+    {
+        let newSelf = Self{
+            archiveFormatComboBox: guiElementProvider.get::<gtk::ComboBoxText>("reportArchiveFormatComboBox"),
+            archiveCompositionComboBox: guiElementProvider.get::<gtk::ComboBoxText>("reportArchiveCompositionComboBox"),
+            fullFilesFolderNameEntry: guiElementProvider.get::<gtk::Entry>("reportFullFilesFolderNameEntry"),
+            diffFileNameEntry: guiElementProvider.get::<gtk::Entry>("reportDiffFileNameEntry"),
+            namesErrorLabel: guiElementProvider.get::<gtk::Label>("reportNamesErrorLabel"),
+            noteCoAuthorsCheckButton: guiElementProvider.get::<gtk::CheckButton>("reportNoteCoAuthorsCheckButton"),
+            encryptZipArchivesCheckButton:
+                guiElementProvider.get::<gtk::CheckButton>("reportEncryptZipArchivesCheckButton"),
+            expandSubmodulesCheckButton: guiElementProvider.get::<gtk::CheckButton>("reportExpandSubmodulesCheckButton")
+        };
+        for format in ArchiveFormat::ALL {
+            newSelf.archiveFormatComboBox.append(Some(format.getId()), format.getLabel());
+        }
+        newSelf.archiveFormatComboBox.set_active_id(Some(settings.archiveFormat.getId()));
+        for composition in ArchiveComposition::ALL {
+            newSelf.archiveCompositionComboBox.append(Some(composition.getId()), composition.getLabel());
+        }
+        newSelf.archiveCompositionComboBox.set_active_id(Some(settings.archiveComposition.getId()));
+        newSelf.fullFilesFolderNameEntry.set_text(&settings.fullFilesFolderName);
+        newSelf.diffFileNameEntry.set_text(&settings.diffFileName);
+        newSelf.noteCoAuthorsCheckButton.set_active(settings.noteCoAuthors);
+        newSelf.encryptZipArchivesCheckButton.set_active(settings.encryptZipArchives);
+        newSelf.expandSubmodulesCheckButton.set_active(settings.expandSubmodules);
+        newSelf.setupEncryptionAvailability();
+        newSelf
+    }
+
+    fn getSettings(&self) -> Result<ReportSettings, String>
+    {
+        let (fullFilesFolderName, diffFileName) =
+            normalizeReportNames(&self.fullFilesFolderNameEntry.text(), &self.diffFileNameEntry.text())?;
+        Ok(ReportSettings{
+            archiveFormat: self.archiveFormatComboBox.active_id()
+                .and_then(|id| ArchiveFormat::fromId(&id))
+                .unwrap_or_default(),
+            archiveComposition: self.archiveCompositionComboBox.active_id()
+                .and_then(|id| ArchiveComposition::fromId(&id))
+                .unwrap_or_default(),
+            fullFilesFolderName,
+            diffFileName,
+            encryptZipArchives: self.encryptZipArchivesCheckButton.is_active(),
+            noteCoAuthors: self.noteCoAuthorsCheckButton.is_active(),
+            expandSubmodules: self.expandSubmodulesCheckButton.is_active()
+        })
+    }
+
+    // Only zip archives can be encrypted, so the option is turned off for other formats.
+    fn setupEncryptionAvailability(&self)
+    {
+        let updateAvailability = {
+            let encryptCheckButton = self.encryptZipArchivesCheckButton.clone();
+            move |comboBox: &gtk::ComboBoxText| {
+                let format = comboBox.active_id().and_then(|id| ArchiveFormat::fromId(&id)).unwrap_or_default();
+                encryptCheckButton.set_sensitive(format == ArchiveFormat::Zip);
+                if format != ArchiveFormat::Zip {
+                    encryptCheckButton.set_active(false);
+                }
+            }
+        };
+        updateAvailability(&self.archiveFormatComboBox);
+        self.archiveFormatComboBox.connect_changed(updateAvailability);
+    }
+}
+
+struct TextNormalizationWidgets
+{
+    sourceEncodingComboBox: gtk::ComboBoxText,
+    lineEndingsComboBox: gtk::ComboBoxText
+}
+
+impl TextNormalizationWidgets
+{
+    fn new(guiElementProvider: &GuiElementProvider, settings: &TextNormalizationSettings) -> Self
+    {
+        let newSelf = Self{
+            sourceEncodingComboBox: guiElementProvider.get::<gtk::ComboBoxText>("sourceEncodingComboBox"),
+            lineEndingsComboBox: guiElementProvider.get::<gtk::ComboBoxText>("lineEndingsComboBox")
+        };
+        for encoding in SourceEncoding::ALL {
+            newSelf.sourceEncodingComboBox.append(Some(encoding.getId()), encoding.getLabel());
+        }
+        newSelf.sourceEncodingComboBox.set_active_id(Some(settings.sourceEncoding.getId()));
+        for lineEndings in LineEndings::ALL {
+            newSelf.lineEndingsComboBox.append(Some(lineEndings.getId()), lineEndings.getLabel());
+        }
+        newSelf.lineEndingsComboBox.set_active_id(Some(settings.lineEndings.getId()));
+        newSelf
+    }
+
+    fn getSettings(&self) -> TextNormalizationSettings
+    {
+        TextNormalizationSettings{
+            sourceEncoding: self.sourceEncodingComboBox.active_id()
+                .and_then(|id| SourceEncoding::fromId(&id))
+                .unwrap_or_default(),
+            lineEndings: self.lineEndingsComboBox.active_id()
+                .and_then(|id| LineEndings::fromId(&id))
+                .unwrap_or_default()
+        }
+    }
+}
+
+// Errors of a previous save are cleared, when the setting is valid now.
+fn showError<T>(errorLabel: &gtk::Label, result: &Result<T, String>)
+{
+    match result {
+        Ok(_) => errorLabel.set_text(""),
+        Err(e) => errorLabel.set_text(e)
+    }
 }
@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use zip::write::SimpleFileOptions as ZipFileOptions;
+use zip::write::ZipWriter;
+
+const REGULAR_FILE_PERMISSIONS: u32 = 0o644;
+const EXECUTABLE_FILE_PERMISSIONS: u32 = 0o755;
+const SYMLINK_PERMISSIONS: u32 = 0o777;
+// the same text git shows for submodules in diffs
+const SUBMODULE_POINTER_PREFIX: &str = "Subproject commit ";
+
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum ArchiveFormat
+{
+    #[default]
+    Zip,
+    TarGz,
+    // smaller and faster than tar.gz, for internal storage
+    TarZst,
+    // uncompressed folders, which can be stored as they are, e.g. in a document management system
+    Directory
+}
+
+impl ArchiveFormat
+{
+    pub const ALL: [ArchiveFormat; 4] = [Self::Zip, Self::TarGz, Self::TarZst, Self::Directory];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Zip       => "zip",
+            Self::TarGz     => "tar.gz",
+            Self::TarZst    => "tar.zst",
+            Self::Directory => "directory"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Zip       => "ZIP (.zip)",
+            Self::TarGz     => "Gzipped tar (.tar.gz)",
+            Self::TarZst    => "Zstandard tar (.tar.zst)",
+            Self::Directory => "Folder (uncompressed)"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|format| format.getId() == id)
+    }
+
+    pub fn makePath(&self, outputPath: &Path, fileNameStem: &str) -> PathBuf
+    {
+        match self {
+            Self::Zip       => outputPath.join(format!("{}.zip", fileNameStem)),
+            Self::TarGz     => outputPath.join(format!("{}.tar.gz", fileNameStem)),
+            Self::TarZst    => outputPath.join(format!("{}.tar.zst", fileNameStem)),
+            Self::Directory => outputPath.join(fileNameStem)
+        }
+    }
+}
+
+// Taken from git, so that permissions of archived files do not depend on the system generating the report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileMode
+{
+    Regular,
+    Executable,
+    // the content of the file is the target of the link, like in git
+    Symlink
+}
+
+impl FileMode
+{
+    pub fn fromGitMode(mode: git2::FileMode) -> Self
+    {
+        match mode {
+            git2::FileMode::BlobExecutable => Self::Executable,
+            git2::FileMode::Link           => Self::Symlink,
+            _ => Self::Regular
+        }
+    }
+
+    fn getPermissions(&self) -> u32
+    {
+        match self {
+            Self::Regular    => REGULAR_FILE_PERMISSIONS,
+            Self::Executable => EXECUTABLE_FILE_PERMISSIONS,
+            Self::Symlink    => SYMLINK_PERMISSIONS
+        }
+    }
+}
+
+// Submodules are stored in archives as files with the commit they point to, as their content is not in the repository.
+pub fn makeSubmodulePointer(commitId: git2::Oid) -> String
+{
+    format!("{}{}\n", SUBMODULE_POINTER_PREFIX, commitId)
+}
+
+// Kept only in memory for the time of generating a report, it is never written to the config.
+#[derive(Clone)]
+pub struct ArchivePassword(String);
+
+impl ArchivePassword
+{
+    pub fn new(password: &str) -> Self
+    {
+        Self(password.into())
+    }
+}
+
+impl std::fmt::Debug for ArchivePassword
+{
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        formatter.write_str("ArchivePassword(<hidden>)")
+    }
+}
+
+// Files of a reported commit, written in one of the archive formats. Paths inside are separated with '/'.
+// Files are only collected until the archive is finished, which is when errors of writing it are returned.
+pub trait ReportArchive
+{
+    fn addFile(&mut self, path: &str, content: &[u8], mode: FileMode);
+    fn finish(self: Box<Self>) -> Result<(), String>;
+}
+
+// Existing archives are not overwritten, so that reports generated earlier do not get lost.
+// Only zip archives can be encrypted, the password is ignored by other formats.
+pub fn createReportArchive(
+    format: ArchiveFormat,
+    path: &Path,
+    modificationTime: &OffsetDateTime,
+    password: Option<&ArchivePassword>)
+    -> Box<dyn ReportArchive>
+{
+    Box::new(SortedArchive{
+        format,
+        path: path.into(),
+        modificationTime: *modificationTime,
+        password: password.cloned(),
+        files: BTreeMap::new(),
+        duplicatePaths: vec![]
+    })
+}
+
+// Files written next to archives, like their manifests, do not overwrite existing ones either.
+pub fn writeNewFile(path: &Path, content: &[u8]) -> Result<(), String>
+{
+    createNewFile(path)
+        .and_then(|mut file| file.write_all(content))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn createNewFile(path: &Path) -> std::io::Result<File>
+{
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+// Writes files ordered by their paths, so that the order does not depend on the order of changes in a commit.
+// Together with times taken from the commit and modes taken from git, regenerating a report gives identical
+// archives, except for encrypted ones, which get a random salt. Nothing is created on disk until it is finished.
+// Paths added more than once fail the archive, as one of the files would get lost.
+struct SortedArchive
+{
+    format: ArchiveFormat,
+    path: PathBuf,
+    modificationTime: OffsetDateTime,
+    password: Option<ArchivePassword>,
+    files: BTreeMap<String, (Vec<u8>, FileMode)>,
+    duplicatePaths: Vec<String>
+}
+
+impl SortedArchive
+{
+    fn write(&self) -> std::io::Result<()>
+    {
+        let (path, time) = (&self.path, &self.modificationTime);
+        let mut writer: Box<dyn ArchiveWriter> = match self.format {
+            ArchiveFormat::Zip       => Box::new(ZipArchive::new(path, time, self.password.as_ref())?),
+            ArchiveFormat::TarGz     => Box::new(TarArchive::newGz(path, time)?),
+            ArchiveFormat::TarZst    => Box::new(TarArchive::newZst(path, time)?),
+            ArchiveFormat::Directory => Box::new(DirectoryArchive::new(path, time)?)
+        };
+        for (path, (content, mode)) in &self.files {
+            writer.writeFile(path, content, *mode)?;
+        }
+        writer.finish()
+    }
+}
+
+impl ReportArchive for SortedArchive
+{
+    fn addFile(&mut self, path: &str, content: &[u8], mode: FileMode)
+    {
+        if self.files.insert(path.into(), (content.into(), mode)).is_some() {
+            self.duplicatePaths.push(path.into());
+        }
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), String>
+    {
+        if let Some(path) = self.duplicatePaths.first() {
+            return Err(format!("Failed to write {}: {} would be stored more than once", self.path.display(), path));
+        }
+        self.write().map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+// Writes files into an archive file or a folder right away, in the order they are given.
+trait ArchiveWriter
+{
+    fn writeFile(&mut self, path: &str, content: &[u8], mode: FileMode) -> std::io::Result<()>;
+    fn finish(self: Box<Self>) -> std::io::Result<()>;
+}
+
+struct ZipArchive
+{
+    writer: ZipWriter<File>,
+    options: ZipFileOptions,
+    password: Option<ArchivePassword>
+}
+
+impl ZipArchive
+{
+    fn new(path: &Path, modificationTime: &OffsetDateTime, password: Option<&ArchivePassword>)
+        -> std::io::Result<Self>
+    {
+        Ok(Self{
+            writer: ZipWriter::new(createNewFile(path)?),
+            options: ZipFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .last_modified_time(toZipDateTime(modificationTime)),
+            password: password.cloned()
+        })
+    }
+}
+
+impl ArchiveWriter for ZipArchive
+{
+    fn writeFile(&mut self, path: &str, content: &[u8], mode: FileMode) -> std::io::Result<()>
+    {
+        let options = self.options.unix_permissions(mode.getPermissions());
+        let options = match &self.password {
+            Some(password) => options.with_aes_encryption(zip::AesMode::Aes256, &password.0),
+            None => options
+        };
+        match mode {
+            FileMode::Symlink => self.writer.add_symlink(path, String::from_utf8_lossy(content), options)?,
+            _ => {
+                self.writer.start_file(path, options)?;
+                self.writer.write_all(content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> std::io::Result<()>
+    {
+        self.writer.finish()?;
+        Ok(())
+    }
+}
+
+fn toZipDateTime(dateTime: &OffsetDateTime) -> zip::DateTime
+{
+    zip::DateTime::from_date_and_time(
+        dateTime.year().try_into().unwrap(),
+        dateTime.month().into(),
+        dateTime.day(),
+        dateTime.hour(),
+        dateTime.minute(),
+        dateTime.second())
+        .unwrap()
+}
+
+// Compressed streams the tar archives are written into.
+trait TarCompression: std::io::Write
+{
+    fn finishCompression(self) -> std::io::Result<()>;
+}
+
+impl TarCompression for flate2::write::GzEncoder<File>
+{
+    fn finishCompression(self) -> std::io::Result<()>
+    {
+        self.finish()?;
+        Ok(())
+    }
+}
+
+impl TarCompression for zstd::Encoder<'static, File>
+{
+    fn finishCompression(self) -> std::io::Result<()>
+    {
+        self.finish()?;
+        Ok(())
+    }
+}
+
+struct TarArchive<Compression: TarCompression>
+{
+    builder: tar::Builder<Compression>,
+    modificationTime: u64
+}
+
+impl TarArchive<flate2::write::GzEncoder<File>>
+{
+    fn newGz(path: &Path, modificationTime: &OffsetDateTime) -> std::io::Result<Self>
+    {
+        let encoder = flate2::write::GzEncoder::new(createNewFile(path)?, flate2::Compression::default());
+        Ok(Self::new(encoder, modificationTime))
+    }
+}
+
+impl TarArchive<zstd::Encoder<'static, File>>
+{
+    fn newZst(path: &Path, modificationTime: &OffsetDateTime) -> std::io::Result<Self>
+    {
+        let encoder = zstd::Encoder::new(createNewFile(path)?, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+        Ok(Self::new(encoder, modificationTime))
+    }
+}
+
+impl<Compression: TarCompression> TarArchive<Compression>
+{
+    fn new(encoder: Compression, modificationTime: &OffsetDateTime) -> Self
+    {
+        Self{
+            builder: tar::Builder::new(encoder),
+            // tar cannot store times before the Unix epoch
+            modificationTime: modificationTime.unix_timestamp().try_into().unwrap_or_default()
+        }
+    }
+}
+
+impl<Compression: TarCompression> ArchiveWriter for TarArchive<Compression>
+{
+    fn writeFile(&mut self, path: &str, content: &[u8], mode: FileMode) -> std::io::Result<()>
+    {
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(mode.getPermissions());
+        header.set_mtime(self.modificationTime);
+        match mode {
+            FileMode::Symlink => {
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_size(0);
+                self.builder.append_link(&mut header, path, &*String::from_utf8_lossy(content))
+            },
+            _ => {
+                header.set_size(content.len().try_into().unwrap());
+                self.builder.append_data(&mut header, path, content)
+            }
+        }
+    }
+
+    fn finish(self: Box<Self>) -> std::io::Result<()>
+    {
+        self.builder.into_inner()?.finishCompression()
+    }
+}
+
+struct DirectoryArchive
+{
+    path: PathBuf,
+    modificationTime: SystemTime
+}
+
+impl DirectoryArchive
+{
+    fn new(path: &Path, modificationTime: &OffsetDateTime) -> std::io::Result<Self>
+    {
+        std::fs::create_dir(path)?;
+        Ok(Self{path: path.into(), modificationTime: (*modificationTime).into()})
+    }
+}
+
+impl ArchiveWriter for DirectoryArchive
+{
+    // permissions of files in folders are left to the system, as not all of them support unix ones
+    fn writeFile(&mut self, path: &str, content: &[u8], mode: FileMode) -> std::io::Result<()>
+    {
+        let filePath = self.path.join(path);
+        std::fs::create_dir_all(filePath.parent().unwrap())?;
+        if mode == FileMode::Symlink && createSymlink(&filePath, content) {
+            return Ok(());
+        }
+        let mut file = createNewFile(&filePath)?;
+        file.write_all(content)?;
+        file.set_modified(self.modificationTime)
+    }
+
+    fn finish(self: Box<Self>) -> std::io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+// Returns false when symlinks are not supported, also by the file system, e.g. on FAT or SMB mounts,
+// then a file with the link target is stored in place of the link.
+#[cfg(unix)]
+fn createSymlink(path: &Path, target: &[u8]) -> bool
+{
+    use std::os::unix::ffi::OsStrExt as _;
+    std::os::unix::fs::symlink(std::ffi::OsStr::from_bytes(target), path).is_ok()
+}
+
+#[cfg(not(unix))]
+fn createSymlink(_path: &Path, _target: &[u8]) -> bool
+{
+    false
+}
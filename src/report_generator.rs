@@ -1,23 +1,29 @@
-use crate::commit_diff::{makeCommitSummary, makeFormattedDiff};
+use crate::commit_annotation::CommitAnnotation;
+use crate::commit_diff::{makeNormalizedFormattedDiff, makeReportCommitSummary};
 use crate::commit_log::{CommitLog, CommitInfo};
+use crate::config_store::Config;
+use crate::date_format::DateFormat;
 use crate::date_time::makeDateTime;
+use crate::diff_settings::DiffSettings;
 use crate::event::{Event, OutputPathInfo};
-use crate::event_handling::{EventHandler, onUnknown};
+use crate::event_handling::{EventHandler, onUnknown, Sender};
+use crate::report_archive::{ArchivePassword, createReportArchive, FileMode, makeSubmodulePointer, ReportArchive};
+use crate::report_archive::writeNewFile;
+use crate::report_manifest::{ManifestArchive, ReportManifest};
+use crate::report_preview::{PreviewArchive, ReportPreview};
+use crate::report_settings::{ANNOTATION_FILE_PATH, ReportSettings, SUBMODULES_FOLDER};
+use crate::report_summary::{makeReportSummary, SUMMARY_FILE_NAME};
 use crate::repository::Repository;
 use crate::source::Source;
+use crate::text_normalization::{TextNormalizationSettings, TextNormalizer};
 
 use std::cell::RefCell;
-use std::fs::File;
-use std::fs::OpenOptions;
-use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
-use time::OffsetDateTime;
-use zip::write::FileOptions as ZipFileOptions;
-use zip::write::ZipWriter;
 
-const DIFF_FILE_PATH: &str = "changes.diff";
+pub const COMMIT_ID_PLACEHOLDER: &str = "<commit_id>";
+pub const COMMIT_SHORT_ID_PLACEHOLDER: &str = "<commit_short_id>";
 
 
 pub struct ReportGenerator
@@ -25,7 +31,28 @@ pub struct ReportGenerator
     commitLog: Rc<RefCell<CommitLog>>,
     repo: Option<Rc<Repository>>,
     outputPath: Option<PathBuf>,
-    outputFileNamesPattern: String
+    outputFileNamesPattern: String,
+    dateFormat: DateFormat,
+    diffSettings: DiffSettings,
+    settings: ReportSettings,
+    textNormalization: TextNormalizationSettings,
+    sender: Sender
+}
+
+// A preview runs the same steps as writing a report, only its archives record what would be written.
+enum ReportMode<'a>
+{
+    Preview(Rc<RefCell<ReportPreview>>),
+    Write(Option<&'a ArchivePassword>)
+}
+
+// A submodule whose pointer was moved by the reported commit, with its checked-out repository.
+struct SubmoduleUpdate
+{
+    path: String,
+    repo: Repository,
+    oldCommitId: Option<git2::Oid>,
+    newCommitId: git2::Oid
 }
 
 impl EventHandler for ReportGenerator
@@ -33,10 +60,16 @@ impl EventHandler for ReportGenerator
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::GenerateReportRequested                => self.generateReport(),
-            Event::OutputFileNamesPatternChanged(pattern) => self.onOutputFileNamesPatternChanged(pattern),
-            Event::OutputPathChanged(pathInfo)            => self.onOutputPathChanged(pathInfo),
-            Event::RepositoryChanged(repo)                => self.onRepositoryChanged(repo),
+            Event::DateFormatChanged(format)                  => self.onDateFormatChanged(*format),
+            Event::DiffSettingsChanged(settings)              => self.onDiffSettingsChanged(settings),
+            Event::GenerateReportRequested                    => self.onGenerateReportRequested(),
+            Event::OutputFileNamesPatternChanged(pattern)     => self.onOutputFileNamesPatternChanged(pattern),
+            Event::OutputPathChanged(pathInfo)                => self.onOutputPathChanged(pathInfo),
+            Event::ReportGenerationConfirmed                  => self.onReportGenerationConfirmed(),
+            Event::ReportPasswordEntered(password)            => self.generateReport(Some(password)),
+            Event::ReportSettingsChanged(settings)            => self.onReportSettingsChanged(settings),
+            Event::RepositoryChanged(repo)                    => self.onRepositoryChanged(repo),
+            Event::TextNormalizationSettingsChanged(settings) => self.onTextNormalizationSettingsChanged(settings),
             _ => onUnknown(source, event)
         }
     }
@@ -45,18 +78,40 @@ impl EventHandler for ReportGenerator
 impl ReportGenerator
 {
     pub fn new(
+        config: &Config,
         commitLog: Rc<RefCell<CommitLog>>,
         repo: Option<Rc<Repository>>,
         outputPath: Option<PathBuf>,
-        outputFileNamesPattern: &str)
+        outputFileNamesPattern: &str,
+        sender: Sender)
         -> Self
     {
-        Self{commitLog, repo, outputPath, outputFileNamesPattern: outputFileNamesPattern.into()}
+        Self{
+            commitLog,
+            repo,
+            outputPath,
+            outputFileNamesPattern: outputFileNamesPattern.into(),
+            dateFormat: config.dateFormat,
+            diffSettings: config.diffSettings.clone(),
+            settings: config.reportSettings.clone(),
+            textNormalization: config.getTextNormalizationSettings(),
+            sender
+        }
     }
 
 
     // private
 
+    fn onDateFormatChanged(&mut self, format: DateFormat)
+    {
+        self.dateFormat = format;
+    }
+
+    fn onDiffSettingsChanged(&mut self, settings: &DiffSettings)
+    {
+        self.diffSettings = settings.clone();
+    }
+
     fn onOutputFileNamesPatternChanged(&mut self, pattern: &str)
     {
         self.outputFileNamesPattern = pattern.into();
@@ -67,7 +122,8 @@ impl ReportGenerator
         self.outputPath = Some(pathInfo.full.clone());
     }
 
-    fn generateReport(&self)
+    // Nothing is written until the preview of the report gets confirmed.
+    fn onGenerateReportRequested(&self)
     {
         let repo = match &self.repo {
             Some(repo) => repo,
@@ -79,56 +135,247 @@ impl ReportGenerator
             None => return
         };
 
-        std::fs::create_dir_all(outputPath).unwrap();
-
-        for commitInfo in self.commitLog.borrow().getCommits() {
-            if !commitInfo.markedForReport {
-                continue;
+        let preview = Rc::new(RefCell::new(ReportPreview::default()));
+        let commitLog = self.commitLog.borrow();
+        if commitLog.getMarkedCommits().next().is_none() {
+            preview.borrow_mut().addWarning("No commits are marked for the report, nothing will be written.".into());
+        }
+        if let Some(warning) = self.makeEncryptionWarning() {
+            preview.borrow_mut().addWarning(warning.into());
+        }
+        for commitInfo in commitLog.getMarkedCommits() {
+            if let Err(e) = self.reportCommit(commitInfo, repo, outputPath, &ReportMode::Preview(Rc::clone(&preview))) {
+                preview.borrow_mut().addError(e);
             }
-            self.reportCommit(commitInfo, repo, outputPath);
+        }
+        if let Some(summary) = self.makeSummary() {
+            preview.borrow_mut().addOtherFile(&outputPath.join(SUMMARY_FILE_NAME), summary.len());
+        }
+        let preview = Rc::try_unwrap(preview).unwrap().into_inner();
+        self.sender.send((Source::ReportGenerator, Event::ReportPreviewPrepared(preview))).unwrap();
+    }
+
+    // The password of encrypted archives is asked for first, the report gets generated once it is entered.
+    fn onReportGenerationConfirmed(&self)
+    {
+        if self.settings.isEncrypted() {
+            self.sender.send((Source::ReportGenerator, Event::ReportPasswordRequested)).unwrap();
+            return;
+        }
+        self.generateReport(None);
+    }
+
+    fn generateReport(&self, password: Option<&ArchivePassword>)
+    {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return
+        };
+
+        let outputPath = match &self.outputPath {
+            Some(path) => path,
+            None => return
+        };
+
+        if let Err(e) = self.writeReport(repo, outputPath, password) {
+            self.sender.send((Source::ReportGenerator, Event::ReportGenerationFailed(e))).unwrap();
+        }
+    }
+
+    // Stops at the first error, archives written until then are left in place.
+    fn writeReport(&self, repo: &Repository, outputPath: &Path, password: Option<&ArchivePassword>)
+        -> Result<(), String>
+    {
+        std::fs::create_dir_all(outputPath)
+            .map_err(|e| format!("Failed to create the output folder {}: {}", outputPath.display(), e))?;
+
+        for commitInfo in self.commitLog.borrow().getMarkedCommits() {
+            self.reportCommit(commitInfo, repo, outputPath, &ReportMode::Write(password))?;
+        }
+        match self.makeSummary() {
+            Some(summary) => writeNewFile(&outputPath.join(SUMMARY_FILE_NAME), summary.as_bytes()),
+            None => Ok(())
+        }
+    }
+
+    // Only contents of zip archives get encrypted, files next to them have to stay readable for verification.
+    fn makeEncryptionWarning(&self) -> Option<&'static str>
+    {
+        match (self.settings.encryptZipArchives, self.settings.isEncrypted()) {
+            (true, false) => Some("Only ZIP archives can be encrypted, the archives will be written unencrypted."),
+            (true, true) => Some("summary.txt and the manifests next to the archives will not be encrypted."),
+            (false, _) => None
+        }
+    }
+
+    // A report without commits gets no summary, so that it does not take the place of a later one.
+    fn makeSummary(&self) -> Option<String>
+    {
+        let commitLog = self.commitLog.borrow();
+        let hasMarkedCommits = commitLog.getMarkedCommits().next().is_some();
+        match hasMarkedCommits {
+            true => Some(makeReportSummary(commitLog.getMarkedCommits(), &self.settings)),
+            false => None
         }
     }
 
+    fn onReportSettingsChanged(&mut self, settings: &ReportSettings)
+    {
+        self.settings = settings.clone();
+    }
+
     fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
     {
         self.repo = Some(Rc::clone(repo));
     }
 
-    fn reportCommit(&self, commitInfo: &CommitInfo, repo: &Repository, outputPath: &Path)
+    fn onTextNormalizationSettingsChanged(&mut self, settings: &TextNormalizationSettings)
+    {
+        self.textNormalization = settings.clone();
+    }
+
+    fn reportCommit(&self, commitInfo: &CommitInfo, repo: &Repository, outputPath: &Path, mode: &ReportMode)
+        -> Result<(), String>
     {
         let commitId = commitInfo.id;
         let commit = repo.findCommit(commitId).unwrap();
-        let commitsDiff = repo.makeDiffOfCommitAndParent(&commit);
-        let commitDateTime = toZipDateTime(&makeDateTime(&commit.time()));
-
-        let zipFileNameStem = self.formatFileName(commitInfo, repo);
-        let fullFilesZipPath = makeFullFilesZipPath(outputPath, &zipFileNameStem);
-        let fullFilesZipFile = OpenOptions::new().write(true).create_new(true).open(fullFilesZipPath).unwrap();
-        let mut fullFilesZipWriter = ZipWriter::new(fullFilesZipFile);
+        // files with changes hidden by the diff settings, like whitespace ones, are still reported in full
+        let commitsDiff = repo.makeDiffOfCommitAndParent(&commit, &DiffSettings::default());
+        let reportedDiff = repo.makeDiffOfCommitAndParent(&commit, &self.diffSettings);
 
-        let diffAndFullFilesZipPath = makeDiffAndFullFilesZipPath(outputPath, &zipFileNameStem);
-        let diffAndFullFilesZipFile = OpenOptions::new().write(true).create_new(true).open(diffAndFullFilesZipPath).unwrap();
-        let mut diffAndFullFilesZipWriter = ZipWriter::new(diffAndFullFilesZipFile);
-
-        let zipOptions = ZipFileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
-            .last_modified_time(commitDateTime);
+        let fileNameStem = self.formatFileName(commitInfo, repo);
+        if commitsDiff.deltas().len() == 0 {
+            warnInPreview(mode, format!("{}: the commit has no changed files", fileNameStem));
+        }
+        let submoduleUpdates = match self.settings.expandSubmodules {
+            true => findSubmoduleUpdates(&commitsDiff, repo, &fileNameStem, mode),
+            false => vec![]
+        };
+        let normalizer = TextNormalizer::new(&self.textNormalization, repo, &commit);
+        let composition = self.settings.archiveComposition;
+        let annotation = match commitInfo.annotation.isEmpty() {
+            true => None,
+            false => Some(&commitInfo.annotation)
+        };
+        if composition.hasFullFilesArchive() {
+            // the annotation goes into the diff archive, unless there is none
+            let annotation = match composition.hasDiffArchive() {
+                true => None,
+                false => annotation
+            };
+            let mut archive =
+                self.createArchive(outputPath, &fileNameStem, &commit, Some(""), annotation.is_some(), mode);
+            if let Some(annotation) = annotation {
+                reportAnnotationFile(annotation, &mut *archive);
+            }
+            reportFullFiles(&commitsDiff, repo, &normalizer, &mut *archive, None);
+            self.reportSubmoduleUpdates(&submoduleUpdates, &mut *archive, Some(""), false);
+            archive.finish()?;
+        }
+        if composition.hasDiffArchive() {
+            let archiveNameStem = format!("{}{}", fileNameStem, composition.getDiffArchiveSuffix());
+            let fullFilesFolder = match composition.hasFullFilesInDiffArchive() {
+                true => Some(self.settings.fullFilesFolderName.as_str()),
+                false => None
+            };
+            let mut archive = self.createArchive(
+                outputPath, &archiveNameStem, &commit, fullFilesFolder, annotation.is_some(), mode);
+            reportDiffFile(
+                &commit, &reportedDiff, repo, self.dateFormat, &normalizer, &self.settings.diffFileName, &mut *archive);
+            if let Some(annotation) = annotation {
+                reportAnnotationFile(annotation, &mut *archive);
+            }
+            if let Some(folder) = fullFilesFolder {
+                reportFullFiles(&commitsDiff, repo, &normalizer, &mut *archive, Some(folder));
+            }
+            self.reportSubmoduleUpdates(&submoduleUpdates, &mut *archive, fullFilesFolder, true);
+            archive.finish()?;
+        }
+        Ok(())
+    }
 
-        reportDiffFile(&commit, &commitsDiff, &mut diffAndFullFilesZipWriter, &zipOptions);
-        reportFullFiles(&commitsDiff, repo, &mut fullFilesZipWriter, &mut diffAndFullFilesZipWriter, &zipOptions);
+    // Changes of each submodule are put into their own folder, with a diff and summaries of all commits in between
+    // its old and new commit. Full files go into the full files folder inside of it, apart from repository files,
+    // or directly into it when full files are in the root of the archive.
+    fn reportSubmoduleUpdates(
+        &self,
+        updates: &[SubmoduleUpdate],
+        archive: &mut dyn ReportArchive,
+        fullFilesFolder: Option<&str>,
+        withDiff: bool)
+    {
+        for update in updates {
+            let submoduleFolder = format!("{}/{}", SUBMODULES_FOLDER, update.path);
+            let newCommit = update.repo.findCommit(update.newCommitId).unwrap();
+            let oldCommit = update.oldCommitId.map(|commitId| update.repo.findCommit(commitId).unwrap());
+            if withDiff {
+                let mut textDiff = String::new();
+                for commitId in update.repo.findCommitsBetween(update.oldCommitId, update.newCommitId) {
+                    let commit = update.repo.findCommit(commitId).unwrap();
+                    textDiff.push_str(&makeReportCommitSummary(&commit, &update.repo, self.dateFormat));
+                    textDiff.push('\n');
+                }
+                let diff = update.repo.makeDiffOfCommits(oldCommit.as_ref(), &newCommit, &self.diffSettings);
+                let normalizer = TextNormalizer::new(&self.textNormalization, &update.repo, &newCommit);
+                textDiff.push_str(&makeNormalizedFormattedDiff(&diff, &normalizer));
+                let diffPath = format!("{}/{}", submoduleFolder, self.settings.diffFileName);
+                archive.addFile(&diffPath, textDiff.as_bytes(), FileMode::Regular);
+            }
+            if let Some(folder) = fullFilesFolder {
+                let folder = match folder.is_empty() {
+                    true => submoduleFolder,
+                    false => format!("{}/{}", submoduleFolder, folder)
+                };
+                let diff = update.repo.makeDiffOfCommits(oldCommit.as_ref(), &newCommit, &DiffSettings::default());
+                let normalizer = TextNormalizer::new(&self.textNormalization, &update.repo, &newCommit);
+                reportFullFiles(&diff, &update.repo, &normalizer, archive, Some(&folder));
+            }
+        }
+    }
 
-        fullFilesZipWriter.finish().unwrap();
-        diffAndFullFilesZipWriter.finish().unwrap();
+    // Full files are put into the given folder of the archive, an empty one stands for its root.
+    fn createArchive(
+        &self,
+        outputPath: &Path,
+        fileNameStem: &str,
+        commit: &git2::Commit,
+        fullFilesFolder: Option<&str>,
+        withAnnotation: bool,
+        mode: &ReportMode)
+        -> Box<dyn ReportArchive>
+    {
+        let archiveFormat = self.settings.archiveFormat;
+        let archivePath = archiveFormat.makePath(outputPath, fileNameStem);
+        let mut manifest = ReportManifest::new(commit, fullFilesFolder);
+        manifest.fileNamePattern = Some(self.outputFileNamesPattern.clone());
+        if self.settings.expandSubmodules {
+            manifest.submodulesFolder = Some(SUBMODULES_FOLDER.into());
+        }
+        if withAnnotation {
+            manifest.annotationFile = Some(ANNOTATION_FILE_PATH.into());
+        }
+        if self.textNormalization.isEnabled() {
+            manifest.textNormalization = Some(self.textNormalization.clone());
+        }
+        match mode {
+            ReportMode::Preview(preview) =>
+                Box::new(PreviewArchive::new(&archivePath, archiveFormat, manifest, Rc::clone(preview))),
+            ReportMode::Write(password)   => {
+                let archive =
+                    createReportArchive(archiveFormat, &archivePath, &makeDateTime(&commit.time()), *password);
+                Box::new(ManifestArchive::new(archive, &archivePath, archiveFormat, manifest))
+            }
+        }
     }
 
     fn formatFileName(&self, commitInfo: &CommitInfo, repo: &Repository) -> String
     {
         let mut fileName = self.outputFileNamesPattern.clone();
-        if fileName.contains("<commit_id>") {
-            fileName = fileName.replace("<commit_id>", &commitInfo.id.to_string());
+        if fileName.contains(COMMIT_ID_PLACEHOLDER) {
+            fileName = fileName.replace(COMMIT_ID_PLACEHOLDER, &commitInfo.id.to_string());
         }
-        if fileName.contains("<commit_short_id>") {
-            fileName = fileName.replace("<commit_short_id>", &makeCommitShortId(commitInfo.id, repo));
+        if fileName.contains(COMMIT_SHORT_ID_PLACEHOLDER) {
+            fileName = fileName.replace(COMMIT_SHORT_ID_PLACEHOLDER, &makeCommitShortId(commitInfo.id, repo));
         }
         if fileName.contains("<commit_summary>") {
             fileName = fileName.replace("<commit_summary>", &commitInfo.summary);
@@ -143,64 +390,90 @@ fn makeCommitShortId(commitId: git2::Oid, repo: &Repository) -> String
     repo.findCommit(commitId).unwrap().as_object().short_id().unwrap().as_str().unwrap().into()
 }
 
-fn makeFullFilesZipPath(outputPath: &Path, fileNameStem: &str) -> PathBuf
+fn warnInPreview(mode: &ReportMode, warning: String)
 {
-    let mut filePath = outputPath.to_owned();
-    filePath.push(format!("{}.zip", fileNameStem));
-    filePath
+    if let ReportMode::Preview(preview) = mode {
+        preview.borrow_mut().addWarning(warning);
+    }
 }
 
-fn makeDiffAndFullFilesZipPath(outputPath: &Path, fileNameStem: &str) -> PathBuf
+// Submodules which are not checked out or miss the reported commits are left only with their pointer files.
+fn findSubmoduleUpdates(
+    commitsDiff: &git2::Diff, repo: &Repository, fileNameStem: &str, mode: &ReportMode) -> Vec<SubmoduleUpdate>
 {
-    let mut filePath = outputPath.to_owned();
-    filePath.push(format!("{}-diff.zip", fileNameStem));
-    filePath
+    let mut updates = vec![];
+    for delta in commitsDiff.deltas() {
+        let newFile = delta.new_file();
+        if delta.status() == git2::Delta::Deleted || newFile.mode() != git2::FileMode::Commit {
+            continue;
+        }
+        let path = newFile.path().unwrap().to_str().unwrap();
+        let submoduleRepo = match repo.openSubmodule(Path::new(path)) {
+            Some(submoduleRepo) => submoduleRepo,
+            None => {
+                warnInPreview(mode, format!("{}: submodule {} is not checked out", fileNameStem, path));
+                continue;
+            }
+        };
+        let oldCommitId = match delta.old_file().mode() {
+            git2::FileMode::Commit => Some(delta.old_file().id()),
+            _ => None
+        };
+        let newCommitId = newFile.id();
+        let hasCommits = [Some(newCommitId), oldCommitId].into_iter().flatten()
+            .all(|commitId| submoduleRepo.findCommit(commitId).is_ok());
+        if !hasCommits {
+            warnInPreview(mode, format!("{}: submodule {} misses the reported commits", fileNameStem, path));
+            continue;
+        }
+        updates.push(SubmoduleUpdate{path: path.into(), repo: submoduleRepo, oldCommitId, newCommitId});
+    }
+    updates
 }
 
 fn reportDiffFile(
     commit: &git2::Commit,
     commitsDiff: &git2::Diff,
-    zipWriter: &mut ZipWriter<File>,
-    zipOptions: &ZipFileOptions)
+    repo: &Repository,
+    dateFormat: DateFormat,
+    normalizer: &TextNormalizer,
+    path: &str,
+    archive: &mut dyn ReportArchive)
+{
+    let textDiff =
+        makeReportCommitSummary(commit, repo, dateFormat) + &makeNormalizedFormattedDiff(commitsDiff, normalizer);
+    archive.addFile(path, textDiff.as_bytes(), FileMode::Regular);
+}
+
+fn reportAnnotationFile(annotation: &CommitAnnotation, archive: &mut dyn ReportArchive)
 {
-    zipWriter.start_file(DIFF_FILE_PATH, *zipOptions).unwrap();
-    let textDiff = makeCommitSummary(commit) + &makeFormattedDiff(commitsDiff);
-    zipWriter.write_all(textDiff.as_bytes()).unwrap();
+    archive.addFile(ANNOTATION_FILE_PATH, annotation.formatForReport().as_bytes(), FileMode::Regular);
 }
 
+// Files are put into the given folder of the archive or into its root. Text files are normalized,
+// while symlinks keep their targets as they are.
 fn reportFullFiles(
     commitsDiff: &git2::Diff,
     repo: &Repository,
-    fullFilesZipWriter: &mut ZipWriter<File>,
-    diffAndFullFilesZipWriter: &mut ZipWriter<File>,
-    zipOptions: &ZipFileOptions)
+    normalizer: &TextNormalizer,
+    archive: &mut dyn ReportArchive,
+    folder: Option<&str>)
 {
     for delta in commitsDiff.deltas() {
         if delta.status() == git2::Delta::Deleted {
             continue;
         }
         let file = delta.new_file();
-        let blob = repo.findBlob(file.id());
-        let fileContent = blob.content();
-
-        let fullFilesFilePath = file.path().unwrap();
-        fullFilesZipWriter.start_file(fullFilesFilePath.to_str().unwrap(), *zipOptions).unwrap();
-        fullFilesZipWriter.write_all(fileContent).unwrap();
-
-        let diffAndFullFilesFilePath = PathBuf::from("full_files").join(file.path().unwrap());
-        diffAndFullFilesZipWriter.start_file(diffAndFullFilesFilePath.to_str().unwrap(), *zipOptions).unwrap();
-        diffAndFullFilesZipWriter.write_all(fileContent).unwrap();
+        let content = match file.mode() {
+            git2::FileMode::Commit => makeSubmodulePointer(file.id()).into_bytes(),
+            git2::FileMode::Link => repo.findBlob(file.id()).content().into(),
+            _ => normalizer.normalizeFile(file.path().unwrap(), repo.findBlob(file.id()).content()).into_owned()
+        };
+        let filePath = file.path().unwrap().to_str().unwrap();
+        let fileMode = FileMode::fromGitMode(file.mode());
+        match folder {
+            Some(folder) => archive.addFile(&format!("{}/{}", folder, filePath), &content, fileMode),
+            None => archive.addFile(filePath, &content, fileMode)
+        }
     }
 }
-
-fn toZipDateTime(dateTime: &OffsetDateTime) -> zip::DateTime
-{
-    zip::DateTime::from_date_and_time(
-        dateTime.year().try_into().unwrap(),
-        dateTime.month().into(),
-        dateTime.day(),
-        dateTime.hour(),
-        dateTime.minute(),
-        dateTime.second())
-        .unwrap()
-}
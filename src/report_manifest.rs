@@ -0,0 +1,265 @@
+use crate::report_archive::{ArchiveFormat, FileMode, ReportArchive, writeNewFile};
+use crate::text_normalization::TextNormalizationSettings;
+
+use sha2::{Digest as _, Sha256};
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE_NAME: &str = "MANIFEST.txt";
+pub const EXTERNAL_MANIFEST_SUFFIX: &str = ".manifest.txt";
+const ARCHIVE_ROOT: &str = ".";
+const TOOL_KEY: &str = "Tool";
+const COMMIT_KEY: &str = "Commit";
+const TREE_KEY: &str = "Tree";
+const FULL_FILES_FOLDER_KEY: &str = "Full files folder";
+const ARCHIVE_KEY: &str = "Archive";
+const SUBMODULES_FOLDER_KEY: &str = "Submodules folder";
+const ANNOTATION_FILE_KEY: &str = "Annotation file";
+const FILE_NAME_PATTERN_KEY: &str = "File name pattern";
+const TEXT_NORMALIZATION_KEY: &str = "Text normalization";
+const HASH_SEPARATOR: &str = "  ";
+// starts lines with escaped paths, like in sha256sum
+const ESCAPED_LINE_PREFIX: &str = "\\";
+
+
+// Lists SHA-256 checksums of files in a report archive. The file lines use the format of sha256sum,
+// so that they can be checked also without this application.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportManifest
+{
+    pub tool: String,
+    pub commitId: String,
+    pub treeId: String,
+    // folder with files taken from the repository, "." for the root of the archive
+    pub fullFilesFolder: Option<String>,
+    // folder in the root of the archive with changes of submodules, which are not in the repository itself
+    pub submodulesFolder: Option<String>,
+    // file in the root of the archive with the annotation of the commit, which is not in the repository either
+    pub annotationFile: Option<String>,
+    // pattern of the archive name, which tells whether the name holds the commit id
+    pub fileNamePattern: Option<String>,
+    // full files converted from their encoding or line endings differ from their blobs in the repository
+    pub textNormalization: Option<TextNormalizationSettings>,
+    // checksum of the archive file itself, only in manifests stored next to archives
+    pub archiveHash: Option<String>,
+    pub files: Vec<ManifestEntry>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestEntry
+{
+    pub hash: String,
+    pub path: String
+}
+
+impl ReportManifest
+{
+    pub fn new(commit: &git2::Commit, fullFilesFolder: Option<&str>) -> Self
+    {
+        Self{
+            tool: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            commitId: commit.id().to_string(),
+            treeId: commit.tree_id().to_string(),
+            fullFilesFolder: fullFilesFolder.map(|folder| match folder.is_empty() {
+                true => ARCHIVE_ROOT.into(),
+                false => folder.into()
+            }),
+            submodulesFolder: None,
+            annotationFile: None,
+            fileNamePattern: None,
+            textNormalization: None,
+            archiveHash: None,
+            files: vec![]
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String>
+    {
+        let mut manifest = Self::default();
+        let mut lines = text.lines();
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            let (key, value) = match line.split_once(": ") {
+                Some(keyAndValue) => keyAndValue,
+                None => return Err(format!("Invalid manifest header line: {}", line))
+            };
+            match key {
+                TOOL_KEY               => manifest.tool = value.into(),
+                COMMIT_KEY             => manifest.commitId = value.into(),
+                TREE_KEY               => manifest.treeId = value.into(),
+                FULL_FILES_FOLDER_KEY  => manifest.fullFilesFolder = Some(value.into()),
+                SUBMODULES_FOLDER_KEY  => manifest.submodulesFolder = Some(value.into()),
+                ANNOTATION_FILE_KEY    => manifest.annotationFile = Some(value.into()),
+                FILE_NAME_PATTERN_KEY  => manifest.fileNamePattern = Some(value.into()),
+                TEXT_NORMALIZATION_KEY => manifest.textNormalization = Some(parseTextNormalization(value)?),
+                ARCHIVE_KEY            => manifest.archiveHash = Some(value.into()),
+                _ => return Err(format!("Unknown manifest header: {}", key))
+            }
+        }
+        if manifest.commitId.is_empty() {
+            return Err("The manifest does not contain a commit id".into());
+        }
+
+        for line in lines {
+            let (isEscaped, unprefixedLine) = match line.strip_prefix(ESCAPED_LINE_PREFIX) {
+                Some(unprefixedLine) => (true, unprefixedLine),
+                None => (false, line)
+            };
+            let (hash, path) = match unprefixedLine.split_once(HASH_SEPARATOR) {
+                Some((hash, path)) if isEscaped => (hash, unescapePath(path)?),
+                Some((hash, path)) => (hash, path.to_string()),
+                None => return Err(format!("Invalid manifest file line: {}", line))
+            };
+            manifest.files.push(ManifestEntry{hash: hash.into(), path});
+        }
+        Ok(manifest)
+    }
+
+    pub fn format(&self) -> String
+    {
+        let mut text = String::new();
+        text.push_str(&format!("{}: {}\n", TOOL_KEY, self.tool));
+        text.push_str(&format!("{}: {}\n", COMMIT_KEY, self.commitId));
+        text.push_str(&format!("{}: {}\n", TREE_KEY, self.treeId));
+        if let Some(folder) = &self.fullFilesFolder {
+            text.push_str(&format!("{}: {}\n", FULL_FILES_FOLDER_KEY, folder));
+        }
+        if let Some(folder) = &self.submodulesFolder {
+            text.push_str(&format!("{}: {}\n", SUBMODULES_FOLDER_KEY, folder));
+        }
+        if let Some(path) = &self.annotationFile {
+            text.push_str(&format!("{}: {}\n", ANNOTATION_FILE_KEY, path));
+        }
+        if let Some(pattern) = &self.fileNamePattern {
+            text.push_str(&format!("{}: {}\n", FILE_NAME_PATTERN_KEY, pattern));
+        }
+        if let Some(settings) = &self.textNormalization {
+            text.push_str(&format!("{}: {}\n", TEXT_NORMALIZATION_KEY, settings.format()));
+        }
+        if let Some(hash) = &self.archiveHash {
+            text.push_str(&format!("{}: {}\n", ARCHIVE_KEY, hash));
+        }
+        text.push('\n');
+        for entry in &self.files {
+            let (prefix, path) = match escapePath(&entry.path) {
+                Some(path) => (ESCAPED_LINE_PREFIX, path),
+                None => ("", entry.path.clone())
+            };
+            text.push_str(&format!("{}{}{}{}\n", prefix, entry.hash, HASH_SEPARATOR, path));
+        }
+        text
+    }
+
+    // Returns the path in the repository of a file from the full files folder. When full files are in the root
+    // of the archive, the submodules folder and the annotation file are there too, but they are not in the repository.
+    pub fn getRepositoryPath<'a>(&self, archivePath: &'a str) -> Option<&'a str>
+    {
+        match self.fullFilesFolder.as_deref() {
+            Some(ARCHIVE_ROOT) => {
+                let isSubmoduleFile = self.submodulesFolder.as_deref().is_some_and(|folder|
+                    archivePath.strip_prefix(folder).is_some_and(|path| path.starts_with('/')));
+                let isAnnotationFile = self.annotationFile.as_deref() == Some(archivePath);
+                match isSubmoduleFile || isAnnotationFile {
+                    true => None,
+                    false => Some(archivePath)
+                }
+            },
+            Some(folder) => archivePath.strip_prefix(folder).and_then(|path| path.strip_prefix('/')),
+            None => None
+        }
+    }
+}
+
+pub fn calculateSha256(content: &[u8]) -> String
+{
+    format!("{:x}", Sha256::digest(content))
+}
+
+pub fn makeExternalManifestPath(archivePath: &Path) -> PathBuf
+{
+    let mut fileName = archivePath.file_name().unwrap().to_owned();
+    fileName.push(EXTERNAL_MANIFEST_SUFFIX);
+    archivePath.with_file_name(fileName)
+}
+
+// Records checksums of files added to an archive. When finished, the manifest is put into the archive
+// and next to it, where it holds also the checksum of the whole archive.
+pub struct ManifestArchive
+{
+    archive: Box<dyn ReportArchive>,
+    archivePath: PathBuf,
+    archiveFormat: ArchiveFormat,
+    manifest: ReportManifest
+}
+
+impl ManifestArchive
+{
+    pub fn new(
+        archive: Box<dyn ReportArchive>,
+        archivePath: &Path,
+        archiveFormat: ArchiveFormat,
+        manifest: ReportManifest)
+        -> Self
+    {
+        Self{archive, archivePath: archivePath.into(), archiveFormat, manifest}
+    }
+}
+
+impl ReportArchive for ManifestArchive
+{
+    fn addFile(&mut self, path: &str, content: &[u8], mode: FileMode)
+    {
+        self.manifest.files.push(ManifestEntry{hash: calculateSha256(content), path: path.into()});
+        self.archive.addFile(path, content, mode);
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), String>
+    {
+        self.manifest.files.sort_by(|first, second| first.path.cmp(&second.path));
+        self.archive.addFile(MANIFEST_FILE_NAME, self.manifest.format().as_bytes(), FileMode::Regular);
+        self.archive.finish()?;
+
+        // folders have no checksum of their own, their files are checked one by one
+        if self.archiveFormat != ArchiveFormat::Directory {
+            let content = std::fs::read(&self.archivePath)
+                .map_err(|e| format!("Failed to read {}: {}", self.archivePath.display(), e))?;
+            self.manifest.archiveHash = Some(calculateSha256(&content));
+        }
+        writeNewFile(&makeExternalManifestPath(&self.archivePath), self.manifest.format().as_bytes())
+    }
+}
+
+// Paths with line breaks or backslashes would break the line format, so these get escaped.
+// Returns None when the path can be stored as it is.
+fn escapePath(path: &str) -> Option<String>
+{
+    if !path.contains(['\\', '\n', '\r']) {
+        return None;
+    }
+    Some(path.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r"))
+}
+
+fn unescapePath(path: &str) -> Result<String, String>
+{
+    let mut unescaped = String::new();
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            _ => return Err(format!("Invalid escaped path in the manifest: {}", path))
+        }
+    }
+    Ok(unescaped)
+}
+
+fn parseTextNormalization(value: &str) -> Result<TextNormalizationSettings, String>
+{
+    TextNormalizationSettings::parse(value).ok_or_else(|| format!("Invalid text normalization: {}", value))
+}
@@ -0,0 +1,153 @@
+use crate::event::Event;
+use crate::event_handling::{EventHandler, onUnknown, Sender};
+use crate::gui_element_provider::GuiElementProvider;
+use crate::report_archive::ArchivePassword;
+use crate::source::Source;
+
+use gtk::prelude::ContainerExt as _;
+use gtk::prelude::DialogExt as _;
+use gtk::prelude::EntryExt as _;
+use gtk::prelude::GridExt as _;
+use gtk::prelude::GtkWindowExt as _;
+use gtk::prelude::LabelExt as _;
+use gtk::prelude::WidgetExt as _;
+
+const SPACING: u32 = 5;
+
+
+// Asks for the password of encrypted report archives each time a report is generated.
+pub struct ReportPasswordDialog
+{
+    parentWindow: gtk::ApplicationWindow,
+    widgets: Option<Widgets>,
+    sender: Sender
+}
+
+struct Widgets
+{
+    dialog: gtk::Dialog,
+    passwordEntry: gtk::Entry,
+    confirmationEntry: gtk::Entry,
+    errorLabel: gtk::Label
+}
+
+impl EventHandler for ReportPasswordDialog
+{
+    fn handle(&mut self, source: Source, event: &Event)
+    {
+        match event {
+            Event::DialogResponded(response) => self.onDialogResponded(*response),
+            Event::ReportPasswordRequested   => self.open(),
+            _ => onUnknown(source, event)
+        }
+    }
+}
+
+impl ReportPasswordDialog
+{
+    pub fn new(guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    {
+        Self{
+            parentWindow: guiElementProvider.get::<gtk::ApplicationWindow>("mainWindow"),
+            widgets: None,
+            sender
+        }
+    }
+
+
+    // private
+
+    fn open(&mut self)
+    {
+        let dialog = gtk::Dialog::with_buttons(
+            Some("Report password"),
+            Some(&self.parentWindow),
+            gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+            &[("Cancel", gtk::ResponseType::Cancel), ("Generate", gtk::ResponseType::Accept)]
+        );
+        dialog.set_default_response(gtk::ResponseType::Accept);
+        let passwordEntry = makePasswordEntry();
+        let confirmationEntry = makePasswordEntry();
+        let errorLabel = gtk::Label::new(None);
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(SPACING);
+        grid.set_column_spacing(SPACING);
+        grid.set_border_width(SPACING);
+        grid.attach(&gtk::Label::new(Some("Password:")), 0, 0, 1, 1);
+        grid.attach(&passwordEntry, 1, 0, 1, 1);
+        grid.attach(&gtk::Label::new(Some("Repeat password:")), 0, 1, 1, 1);
+        grid.attach(&confirmationEntry, 1, 1, 1, 1);
+        grid.attach(&errorLabel, 0, 2, 2, 1);
+        dialog.content_area().add(&grid);
+
+        let sender = self.sender.clone();
+        dialog.connect_response(move |_dialog, response| {
+            sender.send((Source::ReportPasswordDialogWidget, Event::DialogResponded(response))).unwrap();
+        });
+        dialog.show_all();
+        self.widgets = Some(Widgets{dialog, passwordEntry, confirmationEntry, errorLabel});
+    }
+
+    fn onDialogResponded(&mut self, response: gtk::ResponseType)
+    {
+        match response {
+            gtk::ResponseType::Accept      => self.onDialogAccepted(),
+            gtk::ResponseType::Cancel      => self.close(),
+            gtk::ResponseType::DeleteEvent => self.onDialogDeleted(),
+            _ => self.onUnknownDialogResponse(response)
+        }
+    }
+
+    fn onDialogAccepted(&mut self)
+    {
+        let widgets = match &self.widgets {
+            Some(widgets) => widgets,
+            None => {
+                eprintln!("Expected ReportPasswordDialog::widgets to be filled, but it was not");
+                return;
+            }
+        };
+
+        let password = widgets.passwordEntry.text();
+        if password.is_empty() {
+            widgets.errorLabel.set_text("The password cannot be empty.");
+            return;
+        }
+        if password != widgets.confirmationEntry.text() {
+            widgets.errorLabel.set_text("The passwords do not match.");
+            return;
+        }
+        let password = ArchivePassword::new(&password);
+        self.close();
+        self.sender.send((Source::ReportPasswordDialog, Event::ReportPasswordEntered(password))).unwrap();
+    }
+
+    fn onDialogDeleted(&mut self)
+    {
+        self.widgets = None;
+    }
+
+    fn onUnknownDialogResponse(&mut self, response: gtk::ResponseType)
+    {
+        eprintln!("Received unknown dialog response: {:?}", response);
+        self.close();
+    }
+
+    fn close(&mut self)
+    {
+        if let Some(widgets) = &self.widgets {
+            widgets.dialog.close();
+            self.widgets = None;
+        }
+    }
+}
+
+fn makePasswordEntry() -> gtk::Entry
+{
+    let entry = gtk::Entry::new();
+    entry.set_visibility(false);
+    entry.set_input_purpose(gtk::InputPurpose::Password);
+    entry.set_activates_default(true);
+    entry.set_hexpand(true);
+    entry
+}
@@ -0,0 +1,210 @@
+use crate::report_archive::{ArchiveFormat, FileMode, ReportArchive};
+use crate::report_manifest::{calculateSha256, makeExternalManifestPath, ManifestEntry, MANIFEST_FILE_NAME};
+use crate::report_manifest::ReportManifest;
+use crate::text_normalization::isBinary;
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const MAX_FILE_NAME_LENGTH: usize = 255;
+const SIZE_UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+const SIZE_UNIT_STEP: f64 = 1024.0;
+// stands in for the checksum of the archive, which is known only once it is written
+const ARCHIVE_HASH_PLACEHOLDER: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+
+// Archives and files a report would consist of, collected before anything is written to disk.
+// Errors are problems which would make writing the report fail, so it cannot be generated with them.
+#[derive(Debug, Default)]
+pub struct ReportPreview
+{
+    pub archives: Vec<PreviewedArchive>,
+    // manifests of archives and the summary, which are written next to the archives
+    pub otherFiles: Vec<PreviewedOtherFile>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>
+}
+
+#[derive(Debug)]
+pub struct PreviewedArchive
+{
+    pub path: PathBuf,
+    pub files: Vec<PreviewedFile>
+}
+
+#[derive(Debug)]
+pub struct PreviewedOtherFile
+{
+    pub path: PathBuf,
+    pub size: usize
+}
+
+#[derive(Debug)]
+pub struct PreviewedFile
+{
+    pub path: String,
+    pub size: usize,
+    pub isBinary: bool
+}
+
+impl ReportPreview
+{
+    pub fn addWarning(&mut self, warning: String)
+    {
+        self.warnings.push(warning);
+    }
+
+    pub fn addError(&mut self, error: String)
+    {
+        self.errors.push(error);
+    }
+
+    pub fn canBeWritten(&self) -> bool
+    {
+        self.errors.is_empty()
+    }
+
+    // Files other than archives cannot be overwritten either.
+    pub fn addOtherFile(&mut self, path: &Path, size: usize)
+    {
+        if path.exists() {
+            self.addError(format!("{}: the file already exists", path.display()));
+        }
+        self.otherFiles.push(PreviewedOtherFile{path: path.into(), size});
+    }
+
+    pub fn format(&self) -> String
+    {
+        let mut text = String::new();
+        if !self.errors.is_empty() {
+            text.push_str("Errors, which prevent generating the report:\n");
+            for error in &self.errors {
+                text.push_str(&format!("    {}\n", error));
+            }
+            text.push('\n');
+        }
+        if !self.warnings.is_empty() {
+            text.push_str("Warnings:\n");
+            for warning in &self.warnings {
+                text.push_str(&format!("    {}\n", warning));
+            }
+            text.push('\n');
+        }
+
+        let fileCount = self.archives.iter().map(|archive| archive.files.len()).sum::<usize>();
+        let totalSize = self.archives.iter().flat_map(|archive| &archive.files).map(|file| file.size).sum::<usize>()
+            + self.otherFiles.iter().map(|file| file.size).sum::<usize>();
+        text.push_str(&format!(
+            "Archives: {}, files: {}, total size: {}\n", self.archives.len(), fileCount, formatSize(totalSize)));
+        for archive in &self.archives {
+            text.push_str(&format!("\n{}\n", archive.path.display()));
+            for file in &archive.files {
+                let binaryNote = match file.isBinary {
+                    true => " (binary)",
+                    false => ""
+                };
+                text.push_str(&format!("    {:>10}  {}{}\n", formatSize(file.size), file.path, binaryNote));
+            }
+        }
+        if !self.otherFiles.is_empty() {
+            text.push_str("\nNext to the archives:\n");
+            for file in &self.otherFiles {
+                text.push_str(&format!("    {:>10}  {}\n", formatSize(file.size), file.path.display()));
+            }
+        }
+        text
+    }
+
+
+    // private
+
+    fn addArchive(&mut self, archive: PreviewedArchive)
+    {
+        let archiveName = archive.path.display().to_string();
+        if self.archives.iter().any(|previewedArchive| previewedArchive.path == archive.path) {
+            self.addError(format!(
+                "{}: another archive has the same name, e.g. because long names are truncated", archiveName));
+        }
+        if archive.path.exists() {
+            self.addError(format!("{}: the archive already exists", archiveName));
+        }
+        if exceedsMaxFileNameLength(&makeExternalManifestPath(&archive.path)) {
+            self.addError(format!("{}: the name is too long to be stored with its manifest", archiveName));
+        }
+        let mut paths = HashSet::new();
+        for file in archive.files.iter().filter(|file| !paths.insert(&file.path)) {
+            self.addError(format!("{}: {} would be stored more than once", archiveName, file.path));
+        }
+        for file in archive.files.iter().filter(|file| file.isBinary) {
+            self.addWarning(format!("{}: {} is a binary file", archiveName, file.path));
+        }
+        self.archives.push(archive);
+    }
+}
+
+// Stands in for a real archive when previewing a report, it only records what would be added.
+pub struct PreviewArchive
+{
+    archive: PreviewedArchive,
+    archiveFormat: ArchiveFormat,
+    manifest: ReportManifest,
+    preview: Rc<RefCell<ReportPreview>>
+}
+
+impl PreviewArchive
+{
+    pub fn new(
+        archivePath: &Path,
+        archiveFormat: ArchiveFormat,
+        manifest: ReportManifest,
+        preview: Rc<RefCell<ReportPreview>>)
+        -> Self
+    {
+        Self{archive: PreviewedArchive{path: archivePath.into(), files: vec![]}, archiveFormat, manifest, preview}
+    }
+}
+
+impl ReportArchive for PreviewArchive
+{
+    fn addFile(&mut self, path: &str, content: &[u8], _mode: FileMode)
+    {
+        self.manifest.files.push(ManifestEntry{hash: calculateSha256(content), path: path.into()});
+        self.archive.files.push(PreviewedFile{path: path.into(), size: content.len(), isBinary: isBinary(content)});
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), String>
+    {
+        self.manifest.files.sort_by(|first, second| first.path.cmp(&second.path));
+        let manifestSize = self.manifest.format().len();
+        self.archive.files.push(PreviewedFile{path: MANIFEST_FILE_NAME.into(), size: manifestSize, isBinary: false});
+        if self.archiveFormat != ArchiveFormat::Directory {
+            self.manifest.archiveHash = Some(ARCHIVE_HASH_PLACEHOLDER.into());
+        }
+        let externalManifestPath = makeExternalManifestPath(&self.archive.path);
+        let mut preview = self.preview.borrow_mut();
+        preview.addArchive(self.archive);
+        preview.addOtherFile(&externalManifestPath, self.manifest.format().len());
+        Ok(())
+    }
+}
+
+fn exceedsMaxFileNameLength(path: &Path) -> bool
+{
+    path.file_name().is_some_and(|fileName| fileName.len() > MAX_FILE_NAME_LENGTH)
+}
+
+fn formatSize(size: usize) -> String
+{
+    let mut value = size as f64;
+    let mut unitIndex = 0;
+    while value >= SIZE_UNIT_STEP && unitIndex < SIZE_UNITS.len() - 1 {
+        value /= SIZE_UNIT_STEP;
+        unitIndex += 1;
+    }
+    match unitIndex {
+        0 => format!("{} {}", size, SIZE_UNITS[0]),
+        _ => format!("{:.1} {}", value, SIZE_UNITS[unitIndex])
+    }
+}
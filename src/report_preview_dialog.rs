@@ -0,0 +1,123 @@
+use crate::error_dialog::showErrorDialog;
+use crate::event::Event;
+use crate::event_handling::{EventHandler, onUnknown, Sender};
+use crate::gui_element_provider::GuiElementProvider;
+use crate::report_preview::ReportPreview;
+use crate::source::Source;
+
+use gtk::prelude::ContainerExt as _;
+use gtk::prelude::DialogExt as _;
+use gtk::prelude::GtkWindowExt as _;
+use gtk::prelude::TextBufferExt as _;
+use gtk::prelude::TextViewExt as _;
+use gtk::prelude::WidgetExt as _;
+
+const SPACING: u32 = 5;
+const WIDTH: i32 = 800;
+const HEIGHT: i32 = 500;
+
+
+// Lists archives of a report before they are written, the report is generated only when confirmed.
+// Reports with errors in their previews cannot be confirmed, errors of writing them are shown afterwards.
+pub struct ReportPreviewDialog
+{
+    parentWindow: gtk::ApplicationWindow,
+    dialog: Option<gtk::Dialog>,
+    sender: Sender
+}
+
+impl EventHandler for ReportPreviewDialog
+{
+    fn handle(&mut self, source: Source, event: &Event)
+    {
+        match event {
+            Event::DialogResponded(response)      => self.onDialogResponded(*response),
+            Event::ReportGenerationFailed(error)  => self.onReportGenerationFailed(error),
+            Event::ReportPreviewPrepared(preview) => self.open(preview),
+            _ => onUnknown(source, event)
+        }
+    }
+}
+
+impl ReportPreviewDialog
+{
+    pub fn new(guiElementProvider: &GuiElementProvider, sender: Sender) -> Self
+    {
+        Self{
+            parentWindow: guiElementProvider.get::<gtk::ApplicationWindow>("mainWindow"),
+            dialog: None,
+            sender
+        }
+    }
+
+
+    // private
+
+    fn open(&mut self, preview: &ReportPreview)
+    {
+        let dialog = gtk::Dialog::with_buttons(
+            Some("Report preview"),
+            Some(&self.parentWindow),
+            gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+            &[("Cancel", gtk::ResponseType::Cancel), ("Generate", gtk::ResponseType::Accept)]
+        );
+        dialog.set_default_size(WIDTH, HEIGHT);
+        let textView = gtk::TextView::new();
+        textView.set_editable(false);
+        textView.set_monospace(true);
+        textView.buffer().unwrap().set_text(&preview.format());
+        let scrolledWindow = gtk::ScrolledWindow::new(gtk::Adjustment::NONE, gtk::Adjustment::NONE);
+        scrolledWindow.set_vexpand(true);
+        scrolledWindow.set_border_width(SPACING);
+        scrolledWindow.add(&textView);
+        dialog.content_area().add(&scrolledWindow);
+        dialog.set_response_sensitive(gtk::ResponseType::Accept, preview.canBeWritten());
+
+        let sender = self.sender.clone();
+        dialog.connect_response(move |_dialog, response| {
+            sender.send((Source::ReportPreviewDialogWidget, Event::DialogResponded(response))).unwrap();
+        });
+        dialog.show_all();
+        self.dialog = Some(dialog);
+    }
+
+    fn onDialogResponded(&mut self, response: gtk::ResponseType)
+    {
+        match response {
+            gtk::ResponseType::Accept      => self.onDialogAccepted(),
+            gtk::ResponseType::Cancel      => self.close(),
+            gtk::ResponseType::DeleteEvent => self.onDialogDeleted(),
+            _ => self.onUnknownDialogResponse(response)
+        }
+    }
+
+    fn onReportGenerationFailed(&self, error: &str)
+    {
+        showErrorDialog(&self.parentWindow, &format!("Failed to generate the report.\n\n{}", error));
+    }
+
+    fn onDialogAccepted(&mut self)
+    {
+        self.close();
+        self.sender.send((Source::ReportPreviewDialog, Event::ReportGenerationConfirmed)).unwrap();
+    }
+
+    fn onDialogDeleted(&mut self)
+    {
+        self.dialog = None;
+    }
+
+    fn onUnknownDialogResponse(&mut self, response: gtk::ResponseType)
+    {
+        eprintln!("Received unknown dialog response: {:?}", response);
+        self.close();
+    }
+
+    fn close(&mut self)
+    {
+        if let Some(dialog) = &self.dialog {
+            dialog.close();
+            self.dialog = None;
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::report_archive::ArchiveFormat;
+use crate::report_manifest::MANIFEST_FILE_NAME;
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_DIFF_FILE_NAME: &str = "changes.diff";
+const DEFAULT_FULL_FILES_FOLDER_NAME: &str = "full_files";
+// fixed names used inside of archives, which the configurable ones must not take
+pub const ANNOTATION_FILE_PATH: &str = "annotation.txt";
+pub const SUBMODULES_FOLDER: &str = "submodules";
+
+
+// Which archives are generated for each commit. The diff archive holds also the annotation of the commit,
+// which goes into the full files archive when there is no diff archive.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum ArchiveComposition
+{
+    // an archive with full files and another one with the diff and the same full files in a folder
+    #[default]
+    Separate,
+    // one archive with the diff and full files in a folder
+    Combined,
+    DiffOnly,
+    FullFilesOnly
+}
+
+impl ArchiveComposition
+{
+    pub const ALL: [ArchiveComposition; 4] = [Self::Separate, Self::Combined, Self::DiffOnly, Self::FullFilesOnly];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Separate      => "separate",
+            Self::Combined      => "combined",
+            Self::DiffOnly      => "diff_only",
+            Self::FullFilesOnly => "full_files_only"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Separate      => "Full files and diff with full files (two archives)",
+            Self::Combined      => "Diff with full files (one archive)",
+            Self::DiffOnly      => "Only diff",
+            Self::FullFilesOnly => "Only full files"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|composition| composition.getId() == id)
+    }
+
+    pub fn hasFullFilesArchive(&self) -> bool
+    {
+        matches!(self, Self::Separate | Self::FullFilesOnly)
+    }
+
+    pub fn hasDiffArchive(&self) -> bool
+    {
+        !matches!(self, Self::FullFilesOnly)
+    }
+
+    pub fn hasFullFilesInDiffArchive(&self) -> bool
+    {
+        matches!(self, Self::Separate | Self::Combined)
+    }
+
+    // The suffix tells the two archives apart, a single archive does not need it.
+    pub fn getDiffArchiveSuffix(&self) -> &'static str
+    {
+        match self {
+            Self::Separate | Self::DiffOnly => "-diff",
+            Self::Combined | Self::FullFilesOnly => ""
+        }
+    }
+}
+
+// Options of the generated reports.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ReportSettings
+{
+    pub archiveFormat: ArchiveFormat,
+    pub archiveComposition: ArchiveComposition,
+    // names used inside of the diff archive
+    pub fullFilesFolderName: String,
+    pub diffFileName: String,
+    // the password is asked for when generating a report
+    pub encryptZipArchives: bool,
+    // adds the co-authors of pair-programmed commits to the report summary
+    pub noteCoAuthors: bool,
+    // adds changes made in submodules between their old and new commits, when the submodules are checked out
+    pub expandSubmodules: bool
+}
+
+impl Default for ReportSettings
+{
+    fn default() -> Self
+    {
+        Self{
+            archiveFormat: ArchiveFormat::default(),
+            archiveComposition: ArchiveComposition::default(),
+            fullFilesFolderName: DEFAULT_FULL_FILES_FOLDER_NAME.into(),
+            diffFileName: DEFAULT_DIFF_FILE_NAME.into(),
+            encryptZipArchives: false,
+            noteCoAuthors: false,
+            expandSubmodules: false
+        }
+    }
+}
+
+impl ReportSettings
+{
+    pub fn isEncrypted(&self) -> bool
+    {
+        self.encryptZipArchives && self.archiveFormat == ArchiveFormat::Zip
+    }
+}
+
+// Empty names fall back to the default ones, as files cannot be stored under them.
+// Names have to stay inside of archives and must not clash with other files stored there.
+pub fn normalizeReportNames(fullFilesFolderName: &str, diffFileName: &str) -> Result<(String, String), String>
+{
+    let fullFilesFolderName = normalizeName(fullFilesFolderName, DEFAULT_FULL_FILES_FOLDER_NAME, "Full files folder")?;
+    let diffFileName = normalizeName(diffFileName, DEFAULT_DIFF_FILE_NAME, "Diff file")?;
+    let isTaken = |name: &str, takenName: &str| name == takenName || name.starts_with(&format!("{}/", takenName));
+    for takenName in [ANNOTATION_FILE_PATH, MANIFEST_FILE_NAME, SUBMODULES_FOLDER] {
+        if isTaken(&fullFilesFolderName, takenName) || isTaken(&diffFileName, takenName) {
+            return Err(format!("{} is already used inside of archives.", takenName));
+        }
+    }
+    if isTaken(&diffFileName, &fullFilesFolderName) || isTaken(&fullFilesFolderName, &diffFileName) {
+        return Err("The diff file and the full files folder need different names.".into());
+    }
+    Ok((fullFilesFolderName, diffFileName))
+}
+
+fn normalizeName(name: &str, defaultName: &str, description: &str) -> Result<String, String>
+{
+    let name = name.trim().trim_matches('/');
+    if name.is_empty() {
+        return Ok(defaultName.into());
+    }
+    if name.contains('\\') {
+        return Err(format!("{} name cannot contain backslashes: {}", description, name));
+    }
+    if name.split('/').any(|component| matches!(component, "" | "." | "..")) {
+        return Err(format!("{} name cannot contain empty, \".\" or \"..\" path components: {}", description, name));
+    }
+    Ok(name.into())
+}
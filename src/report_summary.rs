@@ -0,0 +1,37 @@
+use crate::commit_annotation::Hours;
+use crate::commit_log::CommitInfo;
+use crate::report_settings::ReportSettings;
+
+
+pub const SUMMARY_FILE_NAME: &str = "summary.txt";
+const COMMIT_PREFIX: &str = "Commit: ";
+// entries are separated by empty lines, which their multi-line fields never contain
+const ENTRY_SEPARATOR: &str = "\n\n";
+// hours are entered by hand, so hundredths are enough and the sum does not show floating point errors
+const TOTAL_HOURS_DECIMALS: usize = 2;
+
+pub fn makeReportSummary<'a>(commits: impl Iterator<Item = &'a CommitInfo>, settings: &ReportSettings) -> String
+{
+    let mut summary = String::new();
+    let mut totalHours: Hours = 0.0;
+    for commit in commits {
+        summary.push_str(&format!("{}{}\nSummary: {}\n", COMMIT_PREFIX, commit.id, commit.summary));
+        if settings.noteCoAuthors && !commit.coAuthors.is_empty() {
+            summary.push_str(&format!("Shared authorship with: {}\n", commit.formatCoAuthors()));
+        }
+        summary.push_str(&format!("{}\n", commit.annotation.formatForReport()));
+        totalHours += commit.annotation.hours.unwrap_or_default();
+    }
+    summary.push_str(&format!("Total hours: {:.*}\n", TOTAL_HOURS_DECIMALS, totalHours));
+    summary
+}
+
+// Returns ids of the commits listed in a report summary. The id is the first line of each entry, so that
+// descriptions cannot add other ones.
+pub fn parseSummaryCommitIds(summary: &str) -> Vec<String>
+{
+    summary.split(ENTRY_SEPARATOR)
+        .filter_map(|entry| entry.lines().next()?.strip_prefix(COMMIT_PREFIX))
+        .map(|commitId| commitId.trim().into())
+        .collect()
+}
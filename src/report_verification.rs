@@ -0,0 +1,424 @@
+use crate::report_archive::makeSubmodulePointer;
+use crate::report_generator::{COMMIT_ID_PLACEHOLDER, COMMIT_SHORT_ID_PLACEHOLDER};
+use crate::report_manifest::{calculateSha256, EXTERNAL_MANIFEST_SUFFIX, MANIFEST_FILE_NAME, ReportManifest};
+use crate::report_summary::{parseSummaryCommitIds, SUMMARY_FILE_NAME};
+use crate::repository::Repository;
+use crate::text_normalization::TextNormalizer;
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const ZIP_EXTENSION: &str = ".zip";
+const TAR_GZ_EXTENSION: &str = ".tar.gz";
+const TAR_ZST_EXTENSION: &str = ".tar.zst";
+const ARCHIVE_EXTENSIONS: [&str; 3] = [ZIP_EXTENSION, TAR_GZ_EXTENSION, TAR_ZST_EXTENSION];
+const DIFF_ARCHIVE_SUFFIX: &str = "-diff";
+const MIN_COMMIT_ID_LENGTH: usize = 7;
+
+
+pub struct VerificationResult
+{
+    pub checkedArchives: usize,
+    pub problems: Vec<String>,
+    // parts of reports, which could not be checked, like contents of encrypted archives
+    pub warnings: Vec<String>
+}
+
+impl VerificationResult
+{
+    pub fn formatTotals(&self) -> String
+    {
+        format!(
+            "Checked archives: {}, problems found: {}, warnings: {}",
+            self.checkedArchives, self.problems.len(), self.warnings.len())
+    }
+}
+
+// Files of an archive by their paths, symlinks with their targets as their content.
+type ArchiveFiles = BTreeMap<String, Vec<u8>>;
+
+enum ArchiveContent
+{
+    Files(ArchiveFiles),
+    Encrypted
+}
+
+// Checks archives of a report folder using manifests stored next to them. Each archive has to match its checksum
+// and files taken from the repository have to match their versions in the reported commit. Subfolders are checked
+// too, so that the whole output folder with its year and month folders can be verified at once.
+pub fn verifyReportFolder(reportFolder: &Path, repo: &Repository) -> VerificationResult
+{
+    let mut result = VerificationResult{checkedArchives: 0, problems: vec![], warnings: vec![]};
+    if let Err(e) = std::fs::read_dir(reportFolder) {
+        result.problems.push(format!("Failed to read the report folder {:?}: {}", reportFolder, e));
+        return result;
+    }
+    verifyFolder(reportFolder, reportFolder, repo, &mut result);
+    result
+}
+
+fn verifyFolder(folder: &Path, reportFolder: &Path, repo: &Repository, result: &mut VerificationResult)
+{
+    let displayName = |path: &Path| path.strip_prefix(reportFolder).unwrap().display().to_string();
+    let mut paths = match std::fs::read_dir(folder) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect::<Vec<_>>(),
+        Err(e) => {
+            result.problems.push(format!("{}: failed to read the folder: {}", displayName(folder), e));
+            return;
+        }
+    };
+    paths.sort();
+
+    let manifestPaths = paths.iter().filter(|path| path.is_file() && isExternalManifest(path)).collect::<Vec<_>>();
+    let mut archivePaths = vec![];
+    let mut reportedCommitIds = vec![];
+    for manifestPath in manifestPaths {
+        let archivePath = makeArchivePath(manifestPath);
+        let archiveName = displayName(&archivePath);
+        let (problems, warnings) = (&mut result.problems, &mut result.warnings);
+        if let Some(manifest) = verifyArchive(manifestPath, &archiveName, repo, problems, warnings) {
+            reportedCommitIds.push(manifest.commitId);
+        }
+        archivePaths.push(archivePath);
+        result.checkedArchives += 1;
+    }
+
+    for path in &paths {
+        let fileName = path.file_name().unwrap().to_string_lossy();
+        if archivePaths.contains(path) || isExternalManifest(path) {
+            continue;
+        }
+        if fileName == SUMMARY_FILE_NAME {
+            verifySummary(path, &displayName(path), &reportedCommitIds, repo, &mut result.problems);
+        } else if path.is_dir() {
+            verifyFolder(path, reportFolder, repo, result);
+        } else if parseArchiveName(&fileName).is_some() {
+            verifyArchiveWithoutManifest(&fileName, &displayName(path), repo, &mut result.problems);
+        } else {
+            result.problems.push(format!("{}: an extra file, which is not a part of any report", displayName(path)));
+        }
+    }
+}
+
+fn isExternalManifest(path: &Path) -> bool
+{
+    path.file_name()
+        .and_then(|fileName| fileName.to_str())
+        .is_some_and(|fileName| fileName.ends_with(EXTERNAL_MANIFEST_SUFFIX))
+}
+
+fn makeArchivePath(manifestPath: &Path) -> PathBuf
+{
+    let manifestFileName = manifestPath.file_name().unwrap().to_str().unwrap();
+    manifestPath.with_file_name(manifestFileName.strip_suffix(EXTERNAL_MANIFEST_SUFFIX).unwrap())
+}
+
+// Returns the name of an archive without its extension and the suffix of diff archives,
+// or None when the name does not belong to a zip, tar.gz or tar.zst archive.
+fn parseArchiveName(fileName: &str) -> Option<&str>
+{
+    let stem = ARCHIVE_EXTENSIONS.iter().find_map(|extension| fileName.strip_suffix(extension))?;
+    Some(stem.strip_suffix(DIFF_ARCHIVE_SUFFIX).unwrap_or(stem))
+}
+
+// Archive names start with the commit id by default, other names cannot be matched with commits. Used only
+// for archives without a manifest, which does not tell the pattern of their names.
+fn findCommitIdInName(archiveName: &str) -> Option<&str>
+{
+    let stem = parseArchiveName(archiveName)
+        .unwrap_or_else(|| archiveName.strip_suffix(DIFF_ARCHIVE_SUFFIX).unwrap_or(archiveName));
+    let firstWord = stem.split(' ').next().unwrap_or_default();
+    match firstWord.len() >= MIN_COMMIT_ID_LENGTH && firstWord.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => Some(firstWord),
+        false => None
+    }
+}
+
+// Files inside of archives are checked against the manifest, whose checksums are then checked against
+// the repository, so each file gets compared with its version in the reported commit too.
+fn verifyArchive(
+    manifestPath: &Path,
+    archiveName: &str,
+    repo: &Repository,
+    problems: &mut Vec<String>,
+    warnings: &mut Vec<String>)
+    -> Option<ReportManifest>
+{
+    let manifestFileName = format!("{}{}", archiveName, EXTERNAL_MANIFEST_SUFFIX);
+    let manifest = match std::fs::read_to_string(manifestPath) {
+        Ok(text) => match ReportManifest::parse(&text) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                problems.push(format!("{}: {}", manifestFileName, e));
+                return None;
+            }
+        },
+        Err(e) => {
+            problems.push(format!("{}: failed to read the manifest: {}", manifestFileName, e));
+            return None;
+        }
+    };
+
+    let archivePath = makeArchivePath(manifestPath);
+    if !archivePath.exists() {
+        problems.push(format!("{}: the archive is missing", archiveName));
+    } else if archivePath.is_dir() {
+        match readFolderFiles(&archivePath) {
+            Ok(files) => verifyArchiveFiles(archiveName, &files, &manifest, problems),
+            Err(e) => problems.push(format!("{}: failed to read the folder: {}", archiveName, e))
+        }
+    } else {
+        verifyArchiveChecksum(archiveName, &archivePath, &manifest, problems);
+        match readArchiveFiles(&archivePath) {
+            Ok(ArchiveContent::Files(files)) => verifyArchiveFiles(archiveName, &files, &manifest, problems),
+            Ok(ArchiveContent::Encrypted) =>
+                warnings.push(format!("{}: the archive is encrypted, only its checksum was checked", archiveName)),
+            Err(e) => problems.push(format!("{}: failed to read the archive: {}", archiveName, e))
+        }
+    }
+    verifyArchiveName(archiveName, archivePath.file_name().unwrap().to_str().unwrap(), &manifest, problems);
+    verifyAgainstRepository(archiveName, &manifest, repo, problems);
+    Some(manifest)
+}
+
+// Only names generated from a pattern starting with the commit id hold it, others can start with any word.
+// The short id can be followed by anything, so the name has to start with at least its minimal length of the id.
+fn verifyArchiveName(archiveName: &str, fileName: &str, manifest: &ReportManifest, problems: &mut Vec<String>)
+{
+    let startsWithCommitId = manifest.fileNamePattern.as_deref().is_some_and(|pattern|
+        pattern.starts_with(COMMIT_ID_PLACEHOLDER) || pattern.starts_with(COMMIT_SHORT_ID_PLACEHOLDER));
+    if !startsWithCommitId {
+        return;
+    }
+    let matchingLength = fileName.chars().zip(manifest.commitId.chars())
+        .take_while(|(first, second)| first == second)
+        .count();
+    if matchingLength < MIN_COMMIT_ID_LENGTH {
+        problems.push(format!(
+            "{}: the name does not match commit {} from the manifest", archiveName, manifest.commitId));
+    }
+}
+
+// Archives generated before manifests were introduced can be located in the repository only by their names.
+fn verifyArchiveWithoutManifest(fileName: &str, archiveName: &str, repo: &Repository, problems: &mut Vec<String>)
+{
+    problems.push(format!("{}: the archive has no manifest", archiveName));
+    if let Some(commitId) = findCommitIdInName(fileName) {
+        if repo.findCommitByPrefix(commitId).is_err() {
+            problems.push(format!("{}: commit {} is not in the repository", archiveName, commitId));
+        }
+    }
+}
+
+fn verifySummary(
+    summaryPath: &Path,
+    summaryName: &str,
+    reportedCommitIds: &[String],
+    repo: &Repository,
+    problems: &mut Vec<String>)
+{
+    let summary = match std::fs::read_to_string(summaryPath) {
+        Ok(summary) => summary,
+        Err(e) => {
+            problems.push(format!("{}: failed to read the summary: {}", summaryName, e));
+            return;
+        }
+    };
+
+    let summaryCommitIds = parseSummaryCommitIds(&summary);
+    for commitId in &summaryCommitIds {
+        if git2::Oid::from_str(commitId).and_then(|commitId| repo.findCommit(commitId)).is_err() {
+            problems.push(format!("{}: commit {} is not in the repository", summaryName, commitId));
+        }
+        if !reportedCommitIds.contains(commitId) {
+            problems.push(format!("{}: commit {} has no archive", summaryName, commitId));
+        }
+    }
+    let mut unlistedCommitIds = reportedCommitIds.iter()
+        .filter(|commitId| !summaryCommitIds.contains(commitId))
+        .collect::<Vec<_>>();
+    unlistedCommitIds.sort();
+    unlistedCommitIds.dedup();
+    for commitId in unlistedCommitIds {
+        problems.push(format!("{}: commit {} has an archive, but is not listed", summaryName, commitId));
+    }
+}
+
+fn verifyArchiveChecksum(archiveName: &str, archivePath: &Path, manifest: &ReportManifest, problems: &mut Vec<String>)
+{
+    let expectedHash = match &manifest.archiveHash {
+        Some(hash) => hash,
+        None => {
+            problems.push(format!("{}: the manifest does not contain a checksum of the archive", archiveName));
+            return;
+        }
+    };
+    match std::fs::read(archivePath) {
+        Ok(content) => {
+            if calculateSha256(&content) != *expectedHash {
+                problems.push(format!("{}: the archive has been modified", archiveName));
+            }
+        },
+        Err(e) => problems.push(format!("{}: failed to read the archive: {}", archiveName, e))
+    }
+}
+
+// The manifest inside has to be the same as the one next to the archive, except for the checksum of the archive,
+// which cannot be known inside of it.
+fn verifyArchiveFiles(archiveName: &str, files: &ArchiveFiles, manifest: &ReportManifest, problems: &mut Vec<String>)
+{
+    for entry in &manifest.files {
+        match files.get(&entry.path) {
+            Some(content) if calculateSha256(content) != entry.hash =>
+                problems.push(format!("{}: {} has been modified", archiveName, entry.path)),
+            Some(_) => (),
+            None => problems.push(format!("{}: {} is missing", archiveName, entry.path))
+        }
+    }
+    for path in files.keys() {
+        if path != MANIFEST_FILE_NAME && !manifest.files.iter().any(|entry| entry.path == *path) {
+            problems.push(format!("{}: {} is an extra file, which is not in the manifest", archiveName, path));
+        }
+    }
+
+    let innerManifest = match files.get(MANIFEST_FILE_NAME) {
+        Some(content) => ReportManifest::parse(&String::from_utf8_lossy(content)),
+        None => return problems.push(format!("{}: {} is missing", archiveName, MANIFEST_FILE_NAME))
+    };
+    match innerManifest {
+        Ok(innerManifest) => {
+            let innerManifest = ReportManifest{archiveHash: manifest.archiveHash.clone(), ..innerManifest};
+            if innerManifest != *manifest {
+                problems.push(format!(
+                    "{}: {} does not match the manifest next to the archive", archiveName, MANIFEST_FILE_NAME));
+            }
+        },
+        Err(e) => problems.push(format!("{}: {}: {}", archiveName, MANIFEST_FILE_NAME, e))
+    }
+}
+
+fn readArchiveFiles(archivePath: &Path) -> Result<ArchiveContent, String>
+{
+    let file = std::fs::File::open(archivePath).map_err(|e| e.to_string())?;
+    match archivePath.to_string_lossy() {
+        path if path.ends_with(ZIP_EXTENSION) => readZipFiles(file).map_err(|e| e.to_string()),
+        path if path.ends_with(TAR_ZST_EXTENSION) => zstd::Decoder::new(file)
+            .and_then(readTarFiles)
+            .map(ArchiveContent::Files)
+            .map_err(|e| e.to_string()),
+        _ => readTarFiles(flate2::read::GzDecoder::new(file)).map(ArchiveContent::Files).map_err(|e| e.to_string())
+    }
+}
+
+fn readZipFiles(file: std::fs::File) -> zip::result::ZipResult<ArchiveContent>
+{
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut files = ArchiveFiles::new();
+    for index in 0..archive.len() {
+        if archive.by_index_raw(index)?.encrypted() {
+            return Ok(ArchiveContent::Encrypted);
+        }
+        let mut zipFile = archive.by_index(index)?;
+        if zipFile.is_dir() {
+            continue;
+        }
+        let mut content = vec![];
+        zipFile.read_to_end(&mut content)?;
+        files.insert(zipFile.name().into(), content);
+    }
+    Ok(ArchiveContent::Files(files))
+}
+
+fn readTarFiles(decoder: impl Read) -> std::io::Result<ArchiveFiles>
+{
+    let mut archive = tar::Archive::new(decoder);
+    let mut files = ArchiveFiles::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+        let content = match entry.header().entry_type() {
+            tar::EntryType::Directory => continue,
+            tar::EntryType::Symlink => entry.link_name_bytes().unwrap_or_default().into_owned(),
+            _ => {
+                let mut content = vec![];
+                entry.read_to_end(&mut content)?;
+                content
+            }
+        };
+        files.insert(path, content);
+    }
+    Ok(files)
+}
+
+fn readFolderFiles(folderPath: &Path) -> std::io::Result<ArchiveFiles>
+{
+    let mut files = ArchiveFiles::new();
+    let mut folders = vec![folderPath.to_path_buf()];
+    while let Some(folder) = folders.pop() {
+        for entry in std::fs::read_dir(&folder)? {
+            let path = entry?.path();
+            if path.is_dir() && !path.is_symlink() {
+                folders.push(path);
+                continue;
+            }
+            let relativePath = path.strip_prefix(folderPath).unwrap().to_string_lossy().replace('\\', "/");
+            files.insert(relativePath, readFolderFile(&path)?);
+        }
+    }
+    Ok(files)
+}
+
+// Symlinks are checked by their targets, which is what the manifest holds for them.
+fn readFolderFile(path: &Path) -> std::io::Result<Vec<u8>>
+{
+    match std::fs::symlink_metadata(path)?.is_symlink() {
+        true => Ok(std::fs::read_link(path)?.into_os_string().into_encoded_bytes()),
+        false => std::fs::read(path)
+    }
+}
+
+fn verifyAgainstRepository(archiveName: &str, manifest: &ReportManifest, repo: &Repository, problems: &mut Vec<String>)
+{
+    let commit = match git2::Oid::from_str(&manifest.commitId).and_then(|commitId| repo.findCommit(commitId)) {
+        Ok(commit) => commit,
+        Err(_) => {
+            problems.push(format!("{}: commit {} is not in the repository", archiveName, manifest.commitId));
+            return;
+        }
+    };
+    if commit.tree_id().to_string() != manifest.treeId {
+        problems.push(format!("{}: the tree of commit {} does not match", archiveName, manifest.commitId));
+        return;
+    }
+
+    // files were normalized when reported, so their blobs have to be normalized the same way to match
+    let textNormalization = manifest.textNormalization.clone().unwrap_or_default();
+    let normalizer = TextNormalizer::new(&textNormalization, repo, &commit);
+    let tree = commit.tree().unwrap();
+    for entry in &manifest.files {
+        let repositoryPath = match manifest.getRepositoryPath(&entry.path) {
+            Some(path) => path,
+            None => continue
+        };
+        let content = match tree.get_path(Path::new(repositoryPath)) {
+            Ok(treeEntry) if treeEntry.filemode() == i32::from(git2::FileMode::Link) =>
+                repo.findBlob(treeEntry.id()).content().to_vec(),
+            Ok(treeEntry) if treeEntry.kind() == Some(git2::ObjectType::Blob) => {
+                let blob = repo.findBlob(treeEntry.id());
+                normalizer.normalizeFile(Path::new(repositoryPath), blob.content()).into_owned()
+            },
+            Ok(treeEntry) if treeEntry.kind() == Some(git2::ObjectType::Commit) =>
+                makeSubmodulePointer(treeEntry.id()).into_bytes(),
+            _ => {
+                problems.push(format!(
+                    "{}: {} is not in commit {}", archiveName, repositoryPath, manifest.commitId));
+                continue;
+            }
+        };
+        if calculateSha256(&content) != entry.hash {
+            problems.push(format!(
+                "{}: {} differs from its version in commit {}", archiveName, repositoryPath, manifest.commitId));
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::config_store::Config;
+use crate::event::{Event, OutputPathInfo};
+use crate::event_handling::{EventHandler, onUnknown};
+use crate::gui_element_provider::GuiElementProvider;
+use crate::report_verification::verifyReportFolder;
+use crate::repository::Repository;
+use crate::source::Source;
+
+use gtk::prelude::ContainerExt as _;
+use gtk::prelude::DialogExt as _;
+use gtk::prelude::GtkWindowExt as _;
+use gtk::prelude::TextBufferExt as _;
+use gtk::prelude::TextViewExt as _;
+use gtk::prelude::WidgetExt as _;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const SPACING: u32 = 5;
+const WIDTH: i32 = 700;
+const HEIGHT: i32 = 400;
+
+
+// Verifies all reports in the output folder and shows the found problems.
+pub struct ReportVerificationDialog
+{
+    parentWindow: gtk::ApplicationWindow,
+    repo: Option<Rc<Repository>>,
+    outputPathPrefix: Option<PathBuf>
+}
+
+impl EventHandler for ReportVerificationDialog
+{
+    fn handle(&mut self, source: Source, event: &Event)
+    {
+        match event {
+            Event::OutputPathChanged(pathInfo) => self.onOutputPathChanged(pathInfo),
+            Event::RepositoryChanged(repo)     => self.onRepositoryChanged(repo),
+            Event::VerifyReportsRequested      => self.onVerifyReportsRequested(),
+            _ => onUnknown(source, event)
+        }
+    }
+}
+
+impl ReportVerificationDialog
+{
+    pub fn new(config: &Config, repo: Option<Rc<Repository>>, guiElementProvider: &GuiElementProvider) -> Self
+    {
+        Self{
+            parentWindow: guiElementProvider.get::<gtk::ApplicationWindow>("mainWindow"),
+            repo,
+            outputPathPrefix: config.outputPathPrefix.clone()
+        }
+    }
+
+
+    // private
+
+    fn onOutputPathChanged(&mut self, pathInfo: &OutputPathInfo)
+    {
+        self.outputPathPrefix = Some(pathInfo.prefix.clone());
+    }
+
+    fn onRepositoryChanged(&mut self, repo: &Rc<Repository>)
+    {
+        self.repo = Some(Rc::clone(repo));
+    }
+
+    fn onVerifyReportsRequested(&self)
+    {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return self.showResult("Choose a repository first, reports are verified against it.")
+        };
+
+        let outputPathPrefix = match &self.outputPathPrefix {
+            Some(path) => path,
+            None => return self.showResult("Choose an output folder first, reports are verified in it.")
+        };
+
+        let result = verifyReportFolder(outputPathPrefix, repo);
+        let mut text = format!("{}\n", result.formatTotals());
+        for problem in &result.problems {
+            text.push_str(&format!("\n{}", problem));
+        }
+        for warning in &result.warnings {
+            text.push_str(&format!("\nWarning: {}", warning));
+        }
+        self.showResult(&text);
+    }
+
+    fn showResult(&self, text: &str)
+    {
+        let dialog = gtk::Dialog::with_buttons(
+            Some("Report verification"),
+            Some(&self.parentWindow),
+            gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+            &[("Close", gtk::ResponseType::Close)]
+        );
+        dialog.set_default_size(WIDTH, HEIGHT);
+        let textView = gtk::TextView::new();
+        textView.set_editable(false);
+        textView.set_monospace(true);
+        textView.buffer().unwrap().set_text(text);
+        let scrolledWindow = gtk::ScrolledWindow::new(gtk::Adjustment::NONE, gtk::Adjustment::NONE);
+        scrolledWindow.set_vexpand(true);
+        scrolledWindow.set_border_width(SPACING);
+        scrolledWindow.add(&textView);
+        dialog.content_area().add(&scrolledWindow);
+
+        // the dialog only shows the result, so it holds no state to be updated on closing
+        dialog.connect_response(|dialog, _response| dialog.close());
+        dialog.show_all();
+    }
+}
@@ -1,9 +1,14 @@
+use crate::diff_settings::DiffSettings;
+
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::path::Path;
 use std::path::PathBuf;
 
+const TEXT_ATTRIBUTE: &str = "text";
+const EOL_ATTRIBUTE: &str = "eol";
+
 
 pub struct Repository
 {
@@ -51,23 +56,107 @@ impl Repository
         }
     }
 
-    pub fn findCommit(&self, id: git2::Oid) -> Result<git2::Commit, git2::Error>
+    pub fn findCommit(&self, id: git2::Oid) -> Result<git2::Commit<'_>, git2::Error>
     {
         self.repo.find_commit(id)
     }
 
-    pub fn findBlob(&self, id: git2::Oid) -> git2::Blob
+    // Finds a commit by an abbreviated id, like the ones in names of report archives.
+    pub fn findCommitByPrefix(&self, idPrefix: &str) -> Result<git2::Commit<'_>, git2::Error>
+    {
+        self.repo.find_commit_by_prefix(idPrefix)
+    }
+
+    pub fn findBlob(&self, id: git2::Oid) -> git2::Blob<'_>
     {
         self.repo.find_blob(id).unwrap()
     }
 
-    pub fn makeDiffOfCommitAndParent(&self, commit: &git2::Commit) -> git2::Diff
+    // Short names of local branches and tags pointing at the commit, with tags prefixed by "tag: ".
+    // Other refs, like remote branches, notes or stashes, are not looked at.
+    pub fn findRefNames(&self, commitId: git2::Oid) -> Vec<String>
+    {
+        let mut names = vec![];
+        for (branch, _branchType) in self.repo.branches(Some(git2::BranchType::Local)).unwrap().flatten() {
+            if branch.get().peel_to_commit().is_ok_and(|commit| commit.id() == commitId) {
+                names.push(String::from_utf8_lossy(branch.get().shorthand_bytes()).into());
+            }
+        }
+        self.repo.tag_foreach(|id, name| {
+            let pointsAtCommit = self.repo.find_object(id, None)
+                .and_then(|object| object.peel_to_commit())
+                .is_ok_and(|commit| commit.id() == commitId);
+            if pointsAtCommit {
+                let name = String::from_utf8_lossy(name);
+                names.push(format!("tag: {}", name.strip_prefix("refs/tags/").unwrap_or(&name)));
+            }
+            true
+        }).unwrap();
+        names
+    }
+
+    // Signatures are only recognized, verifying them would need GnuPG or SSH keys of the signers.
+    pub fn findSignatureKind(&self, commitId: git2::Oid) -> Option<&'static str>
+    {
+        let (signature, _signedData) = self.repo.extract_signature(&commitId, None).ok()?;
+        let signature = String::from_utf8_lossy(&signature);
+        match signature.trim_start() {
+            s if s.starts_with("-----BEGIN PGP SIGNATURE-----") => Some("PGP"),
+            s if s.starts_with("-----BEGIN SSH SIGNATURE-----") => Some("SSH"),
+            s if s.starts_with("-----BEGIN SIGNED MESSAGE-----") => Some("X.509"),
+            _ => Some("unknown")
+        }
+    }
+
+    pub fn makeDiffOfCommitAndParent(&self, commit: &git2::Commit, settings: &DiffSettings) -> git2::Diff<'_>
     {
         let tree = commit.tree().unwrap();
         let parentTreeOpt = findTreeOfParentOfCommit(commit);
-        let mut diffOptions = makeDiffOptions();
+        let mut diffOptions = settings.makeDiffOptions();
         self.repo.diff_tree_to_tree(parentTreeOpt.as_ref(), Some(&tree), Some(&mut diffOptions)).unwrap()
     }
+
+    // Diff between two commits, which do not need to be related, like the old and new commit of a submodule.
+    pub fn makeDiffOfCommits(&self, oldCommit: Option<&git2::Commit>, newCommit: &git2::Commit, settings: &DiffSettings)
+        -> git2::Diff<'_>
+    {
+        let oldTree = oldCommit.map(|commit| commit.tree().unwrap());
+        let mut diffOptions = settings.makeDiffOptions();
+        self.repo.diff_tree_to_tree(oldTree.as_ref(), Some(&newCommit.tree().unwrap()), Some(&mut diffOptions)).unwrap()
+    }
+
+    // Commits reachable from the new commit, but not from the old one, the newest first.
+    pub fn findCommitsBetween(&self, oldCommitId: Option<git2::Oid>, newCommitId: git2::Oid) -> Vec<git2::Oid>
+    {
+        let mut revwalk = self.repo.revwalk().unwrap();
+        revwalk.push(newCommitId).unwrap();
+        if let Some(oldCommitId) = oldCommitId {
+            revwalk.hide(oldCommitId).unwrap();
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL).unwrap();
+        revwalk.map(|oid| oid.unwrap()).collect()
+    }
+
+    // A separate handle of the repository gets the tree of the commit as its index, so that its .gitattributes
+    // files are read instead of the ones in the working directory, while the index of the repository stays intact.
+    pub fn readAttributesOfCommit(&self, commit: &git2::Commit) -> CommitAttributes
+    {
+        let repo = git2::Repository::open(self.repo.path()).unwrap();
+        let mut index = git2::Index::new().unwrap();
+        index.read_tree(&commit.tree().unwrap()).unwrap();
+        repo.set_index(&mut index).unwrap();
+        CommitAttributes{repo}
+    }
+
+    // Returns None when the submodule is not checked out in the working directory.
+    pub fn openSubmodule(&self, submodulePath: &Path) -> Option<Repository>
+    {
+        let path = self.repo.workdir()?.join(submodulePath);
+        match git2::Repository::open(&path) {
+            Ok(repo) => Some(Repository::new(repo, path)),
+            Err(_) => None
+        }
+    }
 }
 
 fn findTreeOfParentOfCommit<'a>(commit: &git2::Commit<'a>) -> Option<git2::Tree<'a>>
@@ -84,11 +173,26 @@ fn findTreeOfParentOfCommit<'a>(commit: &git2::Commit<'a>) -> Option<git2::Tree<
     }
 }
 
-fn makeDiffOptions() -> git2::DiffOptions
+// Attributes from .gitattributes files as they were in a commit, so that reports of it do not change
+// when the attributes get changed later.
+pub struct CommitAttributes
 {
-    let mut diffOptions = git2::DiffOptions::new();
-    diffOptions.indent_heuristic(true);
-    diffOptions
+    repo: git2::Repository
+}
+
+impl CommitAttributes
+{
+    pub fn isMarkedAsNotText(&self, path: &Path) -> bool
+    {
+        let value = self.repo.get_attr(path, TEXT_ATTRIBUTE, git2::AttrCheckFlags::INDEX_ONLY).unwrap_or_default();
+        git2::AttrValue::from_string(value) == git2::AttrValue::False
+    }
+
+    pub fn findEolAttribute(&self, path: &Path) -> Option<String>
+    {
+        let value = self.repo.get_attr(path, EOL_ATTRIBUTE, git2::AttrCheckFlags::INDEX_ONLY).unwrap_or_default();
+        value.map(|value| value.into())
+    }
 }
 
 impl Debug for Repository
@@ -0,0 +1,173 @@
+use crate::word_diff::{CharRange, diffWords};
+
+const FORMATTING_SUCCEEDED: bool = true;
+const LINE_NUMBER_WIDTH: usize = 5;
+// lines of files start with a line number followed by a space
+pub const CONTENT_OFFSET: usize = LINE_NUMBER_WIDTH + 1;
+
+
+pub struct SideBySideDiff
+{
+    pub oldSide: DiffSide,
+    pub newSide: DiffSide
+}
+
+#[derive(Default)]
+pub struct DiffSide
+{
+    pub text: String,
+    pub lineKinds: Vec<LineKind>,
+    // words changed within lines, as line numbers and character ranges in them
+    pub changedWords: Vec<(usize, CharRange)>
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LineKind
+{
+    Normal,
+    FileHeader,
+    HunkHeader,
+    Removed,
+    Added,
+    // empty line inserted to keep both sides aligned
+    Filler
+}
+
+impl DiffSide
+{
+    pub fn findFileHeaderLines(&self) -> Vec<usize>
+    {
+        self.lineKinds.iter().enumerate()
+            .filter(|(_lineNumber, kind)| **kind == LineKind::FileHeader)
+            .map(|(lineNumber, _kind)| lineNumber)
+            .collect()
+    }
+
+    fn push(&mut self, line: &str, kind: LineKind)
+    {
+        self.text.push_str(line);
+        self.text.push('\n');
+        self.lineKinds.push(kind);
+    }
+
+    fn addChangedWords(&mut self, ranges: Vec<CharRange>)
+    {
+        let lineNumber = self.lineKinds.len();
+        self.changedWords.extend(
+            ranges.into_iter().map(|range| (lineNumber, (range.start + CONTENT_OFFSET)..(range.end + CONTENT_OFFSET))));
+    }
+}
+
+// Old and new versions of lines are aligned row by row, so that a removed line is shown next to the line added
+// in its place, while context lines are shown on both sides.
+pub fn makeSideBySideDiff(commitSummary: &str, diff: &git2::Diff) -> SideBySideDiff
+{
+    let mut builder = Builder::default();
+    for line in commitSummary.lines() {
+        builder.oldSide.push(line, LineKind::Normal);
+        builder.newSide.push("", LineKind::Normal);
+    }
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| builder.addLine(&delta, &line)).unwrap();
+    builder.flushChanges();
+    SideBySideDiff{oldSide: builder.oldSide, newSide: builder.newSide}
+}
+
+#[derive(Default)]
+struct Builder
+{
+    oldSide: DiffSide,
+    newSide: DiffSide,
+    removedLines: Vec<ChangedLine>,
+    addedLines: Vec<ChangedLine>
+}
+
+struct ChangedLine
+{
+    lineNumber: Option<u32>,
+    content: String
+}
+
+impl Builder
+{
+    fn addLine(&mut self, delta: &git2::DiffDelta, line: &git2::DiffLine) -> bool
+    {
+        let content = String::from_utf8_lossy(line.content());
+        let content = content.trim_end_matches(['\n', '\r']);
+        match line.origin() {
+            'F' => self.addFileHeader(delta),
+            'H' => self.addHunkHeader(content),
+            ' ' => self.addContextLine(line, content),
+            '-' => self.removedLines.push(ChangedLine{lineNumber: line.old_lineno(), content: content.into()}),
+            '+' => self.addedLines.push(ChangedLine{lineNumber: line.new_lineno(), content: content.into()}),
+            'B' => self.addBoth(content, LineKind::HunkHeader),
+            _ => () // markers of a missing newline at the end of file
+        }
+        FORMATTING_SUCCEEDED
+    }
+
+    fn addFileHeader(&mut self, delta: &git2::DiffDelta)
+    {
+        self.flushChanges();
+        self.oldSide.push(&formatPath(delta.old_file().path()), LineKind::FileHeader);
+        self.newSide.push(&formatPath(delta.new_file().path()), LineKind::FileHeader);
+    }
+
+    fn addHunkHeader(&mut self, header: &str)
+    {
+        self.flushChanges();
+        self.addBoth(header, LineKind::HunkHeader);
+    }
+
+    fn addContextLine(&mut self, line: &git2::DiffLine, content: &str)
+    {
+        self.flushChanges();
+        self.oldSide.push(&formatLine(line.old_lineno(), content), LineKind::Normal);
+        self.newSide.push(&formatLine(line.new_lineno(), content), LineKind::Normal);
+    }
+
+    fn addBoth(&mut self, line: &str, kind: LineKind)
+    {
+        self.oldSide.push(line, kind);
+        self.newSide.push(line, kind);
+    }
+
+    fn flushChanges(&mut self)
+    {
+        let rowCount = self.removedLines.len().max(self.addedLines.len());
+        for row in 0..rowCount {
+            let removedLineOpt = self.removedLines.get(row);
+            let addedLineOpt = self.addedLines.get(row);
+            if let (Some(removedLine), Some(addedLine)) = (removedLineOpt, addedLineOpt) {
+                let wordDiff = diffWords(&removedLine.content, &addedLine.content);
+                self.oldSide.addChangedWords(wordDiff.removed);
+                self.newSide.addChangedWords(wordDiff.added);
+            }
+            match removedLineOpt {
+                Some(line) => self.oldSide.push(&formatLine(line.lineNumber, &line.content), LineKind::Removed),
+                None => self.oldSide.push("", LineKind::Filler)
+            }
+            match addedLineOpt {
+                Some(line) => self.newSide.push(&formatLine(line.lineNumber, &line.content), LineKind::Added),
+                None => self.newSide.push("", LineKind::Filler)
+            }
+        }
+        self.removedLines.clear();
+        self.addedLines.clear();
+    }
+}
+
+fn formatLine(lineNumberOpt: Option<u32>, content: &str) -> String
+{
+    match lineNumberOpt {
+        Some(lineNumber) => format!("{:>width$} {}", lineNumber, content, width = LINE_NUMBER_WIDTH),
+        None => format!("{:>width$} {}", "", content, width = LINE_NUMBER_WIDTH)
+    }
+}
+
+fn formatPath(pathOpt: Option<&std::path::Path>) -> String
+{
+    match pathOpt {
+        Some(path) => path.to_string_lossy().into(),
+        None => "/dev/null".into()
+    }
+}
@@ -0,0 +1,211 @@
+use crate::diff_colorizer::{makeCollapsedSectionTag, setChangedLineColors};
+use crate::diff_colors::DiffColors;
+use crate::event::Event;
+use crate::event_handling::{EventHandler, Sender};
+use crate::line_number::LineNumber;
+use crate::gui_element_provider::GuiElementProvider;
+use crate::side_by_side_diff::{CONTENT_OFFSET, DiffSide, LineKind, SideBySideDiff};
+use crate::source::Source;
+use crate::syntax_highlighter::{LineState, SyntaxHighlighter};
+use crate::text_view::{FontSize, TextView};
+
+use gtk::prelude::TextTagExt as _;
+
+
+pub struct SideBySideDiffView
+{
+    oldSide: SideView,
+    newSide: SideView
+}
+
+impl SideBySideDiffView
+{
+    pub fn new(
+        guiElementProvider: &GuiElementProvider,
+        colors: &DiffColors,
+        fontSize: Option<FontSize>,
+        sender: Sender)
+        -> Self
+    {
+        let newSelf = Self{
+            oldSide: SideView::new(guiElementProvider, "commitDiffOldView", colors, fontSize, sender.clone()),
+            newSide: SideView::new(guiElementProvider, "commitDiffNewView", colors, fontSize, sender)
+        };
+        newSelf.setColors(colors);
+        newSelf
+    }
+
+    pub fn setColors(&self, colors: &DiffColors)
+    {
+        self.oldSide.setColors(colors, &colors.removedWord);
+        self.newSide.setColors(colors, &colors.addedWord);
+    }
+
+    pub fn show(&self, diff: &SideBySideDiff)
+    {
+        self.oldSide.show(&diff.oldSide);
+        self.newSide.show(&diff.newSide);
+    }
+
+    pub fn clear(&self)
+    {
+        self.oldSide.textView.clear();
+        self.newSide.textView.clear();
+    }
+
+    pub fn setSectionShown(&self, startLine: LineNumber, endLine: LineNumber, isShown: bool)
+    {
+        self.oldSide.setSectionShown(startLine, endLine, isShown);
+        self.newSide.setSectionShown(startLine, endLine, isShown);
+    }
+
+    pub fn getTextViews(&self) -> [&TextView; 2]
+    {
+        [&self.oldSide.textView, &self.newSide.textView]
+    }
+
+    // both sides share the vertical scrollbar, so scrolling one of them is enough
+    pub fn scrollToLine(&self, line: LineNumber)
+    {
+        self.oldSide.textView.scrollToLine(line);
+    }
+
+    pub fn zoom(&mut self, source: Source, event: &Event)
+    {
+        self.oldSide.textView.handle(source, event);
+        self.newSide.textView.handle(source, event);
+    }
+}
+
+struct SideView
+{
+    textView: TextView,
+    tags: LineTags,
+    syntaxHighlighter: SyntaxHighlighter
+}
+
+impl SideView
+{
+    fn new(
+        guiElementProvider: &GuiElementProvider,
+        name: &str,
+        colors: &DiffColors,
+        fontSize: Option<FontSize>,
+        sender: Sender)
+        -> Self
+    {
+        let textView = TextView::new(guiElementProvider, name, fontSize, sender, Source::CommitDiffViewWidget);
+        let tags = LineTags::new();
+        textView.registerTags(
+            &[&tags.added, &tags.removed, &tags.hunkHeader, &tags.fileHeader, &tags.filler, &tags.changedWord,
+              &tags.collapsedSection]);
+        let syntaxHighlighter = SyntaxHighlighter::new(colors);
+        syntaxHighlighter.setupTextView(&textView);
+        Self{textView, tags, syntaxHighlighter}
+    }
+
+    fn setColors(&self, colors: &DiffColors, changedWordColor: &str)
+    {
+        self.tags.setColors(colors, changedWordColor);
+        self.syntaxHighlighter.setColors(colors);
+    }
+
+    fn show(&self, side: &DiffSide)
+    {
+        self.textView.setText(&side.text);
+        // neighbouring lines of the same kind are tagged at once, which is much faster than tagging line by line
+        let mut runStart = 0;
+        for (lineNumber, kind) in side.lineKinds.iter().enumerate() {
+            if *kind != side.lineKinds[runStart] {
+                self.applyTag(side.lineKinds[runStart], runStart, lineNumber);
+                runStart = lineNumber;
+            }
+        }
+        if let Some(kind) = side.lineKinds.get(runStart) {
+            self.applyTag(*kind, runStart, side.lineKinds.len());
+        }
+        for (lineNumber, charRange) in &side.changedWords {
+            self.textView.applyTagInLine(&self.tags.changedWord, (*lineNumber).into(), charRange);
+        }
+        self.applySyntaxTags(side);
+    }
+
+    fn setSectionShown(&self, startLine: LineNumber, endLine: LineNumber, isShown: bool)
+    {
+        match isShown {
+            true => self.textView.removeTag(&self.tags.collapsedSection, startLine, endLine),
+            false => self.textView.applyTag(&self.tags.collapsedSection, startLine, endLine)
+        }
+    }
+
+    fn applySyntaxTags(&self, side: &DiffSide)
+    {
+        let mut lineState = LineState::default();
+        for (lineNumber, (line, kind)) in side.text.lines().zip(&side.lineKinds).enumerate() {
+            match kind {
+                LineKind::FileHeader => lineState = LineState::forFile(line),
+                LineKind::Normal | LineKind::Removed | LineKind::Added => self.syntaxHighlighter.highlightLine(
+                    &self.textView, lineNumber.into(), line, CONTENT_OFFSET, &mut lineState),
+                LineKind::HunkHeader | LineKind::Filler => ()
+            }
+        }
+    }
+
+    fn applyTag(&self, kind: LineKind, startLine: usize, endLine: usize)
+    {
+        if let Some(tag) = self.tags.get(kind) {
+            self.textView.applyTag(tag, startLine.into(), endLine.into());
+        }
+    }
+}
+
+struct LineTags
+{
+    added: gtk::TextTag,
+    removed: gtk::TextTag,
+    hunkHeader: gtk::TextTag,
+    fileHeader: gtk::TextTag,
+    filler: gtk::TextTag,
+    changedWord: gtk::TextTag,
+    collapsedSection: gtk::TextTag
+}
+
+impl LineTags
+{
+    fn new() -> Self
+    {
+        Self{
+            added: gtk::TextTag::new(Some("addedLine")),
+            removed: gtk::TextTag::new(Some("removedLine")),
+            hunkHeader: gtk::TextTag::new(Some("hunkHeader")),
+            fileHeader: gtk::TextTag::new(Some("fileHeader")),
+            filler: gtk::TextTag::new(Some("filler")),
+            changedWord: gtk::TextTag::new(Some("changedWord")),
+            collapsedSection: makeCollapsedSectionTag()
+        }
+    }
+
+    fn setColors(&self, colors: &DiffColors, changedWordColor: &str)
+    {
+        setChangedLineColors(
+            &self.added, &colors.addedLineForeground, &colors.addedLineBackground, colors.highlighting);
+        setChangedLineColors(
+            &self.removed, &colors.removedLineForeground, &colors.removedLineBackground, colors.highlighting);
+        self.hunkHeader.set_foreground(Some(&colors.hunkHeader));
+        self.fileHeader.set_foreground(Some(&colors.fileHeader));
+        self.filler.set_paragraph_background(Some(&colors.filler));
+        self.changedWord.set_background(Some(changedWordColor));
+    }
+
+    fn get(&self, kind: LineKind) -> Option<&gtk::TextTag>
+    {
+        match kind {
+            LineKind::Normal     => None,
+            LineKind::FileHeader => Some(&self.fileHeader),
+            LineKind::HunkHeader => Some(&self.hunkHeader),
+            LineKind::Removed    => Some(&self.removed),
+            LineKind::Added      => Some(&self.added),
+            LineKind::Filler     => Some(&self.filler)
+        }
+    }
+}
@@ -8,12 +8,21 @@ pub enum Source
     ChooseRepositoryFolderButton,
     ChooseRepositoryFolderButtonWidget,
     ChooseRepositoryFolderDialog,
+    CommitDiffFilesViewCheckButton,
+    CommitDiffFilesViewWidget,
+    CommitDiffSearchBar,
+    CommitDiffView,
     CommitDiffViewWidget,
     CommitAuthorFilterEntry,
     CommitLog,
+    CommitLogModel,
     CommitLogModelFilter,
     CommitLogView,
     CommitLogViewCheckButton,
+    CommitLogViewCreativeWorkCell,
+    CommitLogViewDescriptionCell,
+    CommitLogViewHoursCell,
+    CommitLogViewMenu,
     CommitLogViewWidget,
     GenerateReportButton,
     MonthComboBox,
@@ -22,6 +31,13 @@ pub enum Source
     OptionsDialogWidget,
     OutputPathStore,
     PaneWithCommitLogAndDiff,
+    ReportGenerator,
+    ReportPasswordDialog,
+    ReportPasswordDialogWidget,
+    ReportPreviewDialog,
+    ReportPreviewDialogWidget,
     RepositoryStore,
+    SideBySideDiffToggleButton,
+    VerifyReportsButton,
     YearSpinButton
 }
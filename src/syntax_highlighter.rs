@@ -0,0 +1,275 @@
+use crate::diff_colors::DiffColors;
+use crate::line_number::LineNumber;
+use crate::text_view::TextView;
+use crate::word_diff::CharRange;
+
+use gtk::prelude::TextTagExt as _;
+use std::path::Path;
+
+
+pub struct SyntaxHighlighter
+{
+    keywordTag: gtk::TextTag,
+    stringTag: gtk::TextTag,
+    commentTag: gtk::TextTag,
+    numberTag: gtk::TextTag
+}
+
+impl SyntaxHighlighter
+{
+    pub fn new(colors: &DiffColors) -> Self
+    {
+        let newSelf = Self{
+            keywordTag: gtk::TextTag::new(Some("syntaxKeyword")),
+            stringTag: gtk::TextTag::new(Some("syntaxString")),
+            commentTag: gtk::TextTag::new(Some("syntaxComment")),
+            numberTag: gtk::TextTag::new(Some("syntaxNumber"))
+        };
+        newSelf.setColors(colors);
+        newSelf
+    }
+
+    pub fn setColors(&self, colors: &DiffColors)
+    {
+        self.keywordTag.set_foreground(Some(&colors.syntaxKeyword));
+        self.stringTag.set_foreground(Some(&colors.syntaxString));
+        self.commentTag.set_foreground(Some(&colors.syntaxComment));
+        self.numberTag.set_foreground(Some(&colors.syntaxNumber));
+    }
+
+    // Has to be called after registering tags of diff lines, so that syntax colors have higher priority.
+    pub fn setupTextView(&self, textView: &TextView)
+    {
+        textView.registerTags(&[&self.keywordTag, &self.stringTag, &self.commentTag, &self.numberTag]);
+    }
+
+    pub fn highlightLine(
+        &self, textView: &TextView, lineNumber: LineNumber, line: &str, contentOffset: usize, state: &mut LineState)
+    {
+        let language = match state.language {
+            Some(language) => language,
+            None => return
+        };
+
+        let content = line.chars().skip(contentOffset).collect::<String>();
+        for (kind, range) in tokenizeLine(language, &content, &mut state.isInsideBlockComment) {
+            let range = (range.start + contentOffset)..(range.end + contentOffset);
+            textView.applyTagInLine(self.getTag(kind), lineNumber, &range);
+        }
+    }
+
+
+    // private
+
+    fn getTag(&self, kind: SyntaxKind) -> &gtk::TextTag
+    {
+        match kind {
+            SyntaxKind::Keyword => &self.keywordTag,
+            SyntaxKind::String  => &self.stringTag,
+            SyntaxKind::Comment => &self.commentTag,
+            SyntaxKind::Number  => &self.numberTag
+        }
+    }
+}
+
+// Highlighting state of a file within a diff, block comments can span several lines.
+#[derive(Default)]
+pub struct LineState
+{
+    language: Option<&'static Language>,
+    isInsideBlockComment: bool
+}
+
+impl LineState
+{
+    pub fn forFile(path: &str) -> Self
+    {
+        Self{language: findLanguage(path), isInsideBlockComment: false}
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SyntaxKind
+{
+    Keyword,
+    String,
+    Comment,
+    Number
+}
+
+struct Language
+{
+    extensions: &'static [&'static str],
+    keywords: &'static [&'static str],
+    lineComment: Option<&'static str>,
+    blockComment: Option<(&'static str, &'static str)>,
+    stringDelimiters: &'static [char]
+}
+
+const C_LIKE_KEYWORDS: &[&str] = &[
+    "break", "case", "catch", "class", "const", "continue", "default", "delete", "do", "else", "enum", "extern",
+    "false", "for", "goto", "if", "inline", "namespace", "new", "nullptr", "private", "protected", "public", "return",
+    "sizeof", "static", "struct", "switch", "template", "this", "throw", "true", "try", "typedef", "typename", "union",
+    "using", "virtual", "void", "volatile", "while"];
+
+const LANGUAGES: &[Language] = &[
+    Language{
+        extensions: &["rs"],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false",
+            "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+            "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while"],
+        lineComment: Some("//"),
+        blockComment: Some(("/*", "*/")),
+        stringDelimiters: &['"']
+    },
+    Language{
+        extensions: &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+        keywords: &[
+            "abstract", "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+            "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for", "from", "function", "if",
+            "implements", "import", "in", "instanceof", "interface", "let", "new", "null", "private", "protected",
+            "public", "readonly", "return", "static", "super", "switch", "this", "throw", "true", "try", "type",
+            "typeof", "undefined", "var", "void", "while", "yield"],
+        lineComment: Some("//"),
+        blockComment: Some(("/*", "*/")),
+        stringDelimiters: &['"', '\'', '`']
+    },
+    Language{
+        extensions: &["c", "h", "cc", "cpp", "cxx", "hpp", "hh", "java", "cs"],
+        keywords: C_LIKE_KEYWORDS,
+        lineComment: Some("//"),
+        blockComment: Some(("/*", "*/")),
+        stringDelimiters: &['"', '\'']
+    },
+    Language{
+        extensions: &["go"],
+        keywords: &[
+            "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "false", "for",
+            "func", "go", "goto", "if", "import", "interface", "map", "nil", "package", "range", "return", "select",
+            "struct", "switch", "true", "type", "var"],
+        lineComment: Some("//"),
+        blockComment: Some(("/*", "*/")),
+        stringDelimiters: &['"', '`']
+    },
+    Language{
+        extensions: &["py"],
+        keywords: &[
+            "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else",
+            "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "None",
+            "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield"],
+        lineComment: Some("#"),
+        blockComment: None,
+        stringDelimiters: &['"', '\'']
+    },
+    Language{
+        extensions: &["sh", "bash"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in", "local",
+            "return", "then", "while"],
+        lineComment: Some("#"),
+        blockComment: None,
+        stringDelimiters: &['"', '\'']
+    },
+    Language{
+        extensions: &["toml", "yaml", "yml"],
+        keywords: &["true", "false"],
+        lineComment: Some("#"),
+        blockComment: None,
+        stringDelimiters: &['"', '\'']
+    }
+];
+
+fn findLanguage(path: &str) -> Option<&'static Language>
+{
+    let extension = Path::new(path).extension()?.to_str()?;
+    LANGUAGES.iter().find(|language| language.extensions.contains(&extension))
+}
+
+fn tokenizeLine(language: &Language, line: &str, isInsideBlockComment: &mut bool) -> Vec<(SyntaxKind, CharRange)>
+{
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let start = index;
+        if *isInsideBlockComment {
+            let (_, blockCommentEnd) = language.blockComment.unwrap();
+            index = match findFrom(&chars, index, blockCommentEnd) {
+                Some(endIndex) => {
+                    *isInsideBlockComment = false;
+                    endIndex + blockCommentEnd.chars().count()
+                },
+                None => chars.len()
+            };
+            tokens.push((SyntaxKind::Comment, start..index));
+            continue;
+        }
+
+        if let Some(lineComment) = language.lineComment {
+            if startsWith(&chars, index, lineComment) {
+                tokens.push((SyntaxKind::Comment, start..chars.len()));
+                break;
+            }
+        }
+        if let Some((blockCommentStart, _)) = language.blockComment {
+            if startsWith(&chars, index, blockCommentStart) {
+                *isInsideBlockComment = true;
+                index += blockCommentStart.chars().count();
+                // the rest of the comment is handled in the next iteration
+                tokens.push((SyntaxKind::Comment, start..index));
+                continue;
+            }
+        }
+
+        let character = chars[index];
+        if language.stringDelimiters.contains(&character) {
+            index = findStringEnd(&chars, index + 1, character);
+            tokens.push((SyntaxKind::String, start..index));
+        } else if character.is_ascii_digit() {
+            index = skipWhile(&chars, index, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            tokens.push((SyntaxKind::Number, start..index));
+        } else if character.is_alphabetic() || character == '_' {
+            index = skipWhile(&chars, index, |c| c.is_alphanumeric() || c == '_');
+            let word = chars[start..index].iter().collect::<String>();
+            if language.keywords.contains(&word.as_str()) {
+                tokens.push((SyntaxKind::Keyword, start..index));
+            }
+        } else {
+            index += 1;
+        }
+    }
+    tokens
+}
+
+fn startsWith(chars: &[char], index: usize, pattern: &str) -> bool
+{
+    let patternChars = pattern.chars().collect::<Vec<_>>();
+    chars.get(index..index + patternChars.len()) == Some(&patternChars[..])
+}
+
+fn findFrom(chars: &[char], index: usize, pattern: &str) -> Option<usize>
+{
+    (index..chars.len()).find(|index| startsWith(chars, *index, pattern))
+}
+
+// Unterminated strings end with the line.
+fn findStringEnd(chars: &[char], mut index: usize, delimiter: char) -> usize
+{
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 2,
+            c if c == delimiter => return index + 1,
+            _ => index += 1
+        }
+    }
+    chars.len()
+}
+
+fn skipWhile(chars: &[char], mut index: usize, predicate: impl Fn(char) -> bool) -> usize
+{
+    while index < chars.len() && predicate(chars[index]) {
+        index += 1;
+    }
+    index
+}
@@ -0,0 +1,289 @@
+use crate::repository::{CommitAttributes, Repository};
+
+use encoding_rs::Encoding;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+// the same amount of content git checks to tell binary files apart
+const BINARY_CHECK_LENGTH: usize = 8000;
+const LF: &str = "\n";
+const CRLF: &str = "\r\n";
+const EOL_LF_VALUE: &str = "lf";
+const EOL_CRLF_VALUE: &str = "crlf";
+
+
+// Files which are valid UTF-8 are always decoded as UTF-8, as repositories can mix them with legacy ones.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum SourceEncoding
+{
+    // files which are not valid UTF-8 keep their bytes, only their line endings get converted
+    #[default]
+    Utf8,
+    // files which are not valid UTF-8 get their encoding guessed from their content
+    Detect,
+    Windows1250,
+    Windows1252,
+    Iso8859_2
+}
+
+impl SourceEncoding
+{
+    pub const ALL: [SourceEncoding; 5] =
+        [Self::Utf8, Self::Detect, Self::Windows1250, Self::Windows1252, Self::Iso8859_2];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Utf8        => "utf-8",
+            Self::Detect      => "detect",
+            Self::Windows1250 => "windows-1250",
+            Self::Windows1252 => "windows-1252",
+            Self::Iso8859_2   => "iso-8859-2"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Utf8        => "UTF-8",
+            Self::Detect      => "Detect from the content",
+            Self::Windows1250 => "Windows-1250 (Central European)",
+            Self::Windows1252 => "Windows-1252 (Western European)",
+            Self::Iso8859_2   => "ISO-8859-2 (Latin-2)"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|encoding| encoding.getId() == id)
+    }
+
+    fn findEncoding(&self, content: &[u8]) -> &'static Encoding
+    {
+        match self {
+            Self::Utf8        => encoding_rs::UTF_8,
+            Self::Detect      => detectEncoding(content),
+            Self::Windows1250 => encoding_rs::WINDOWS_1250,
+            Self::Windows1252 => encoding_rs::WINDOWS_1252,
+            Self::Iso8859_2   => encoding_rs::ISO_8859_2
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum LineEndings
+{
+    #[default]
+    Keep,
+    Lf,
+    Crlf
+}
+
+impl LineEndings
+{
+    pub const ALL: [LineEndings; 3] = [Self::Keep, Self::Lf, Self::Crlf];
+
+    pub fn getId(&self) -> &'static str
+    {
+        match self {
+            Self::Keep => "keep",
+            Self::Lf   => "lf",
+            Self::Crlf => "crlf"
+        }
+    }
+
+    pub fn getLabel(&self) -> &'static str
+    {
+        match self {
+            Self::Keep => "Keep as in the repository",
+            Self::Lf   => "LF (Unix)",
+            Self::Crlf => "CRLF (Windows)"
+        }
+    }
+
+    pub fn fromId(id: &str) -> Option<Self>
+    {
+        Self::ALL.into_iter().find(|lineEndings| lineEndings.getId() == id)
+    }
+
+    fn getLineEnding(&self) -> Option<&'static str>
+    {
+        match self {
+            Self::Keep => None,
+            Self::Lf   => Some(LF),
+            Self::Crlf => Some(CRLF)
+        }
+    }
+}
+
+// How text files of a repository are converted for reports. Stored per repository, as legacy ones may use
+// other encodings than the rest.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TextNormalizationSettings
+{
+    pub sourceEncoding: SourceEncoding,
+    pub lineEndings: LineEndings
+}
+
+impl TextNormalizationSettings
+{
+    pub fn isEnabled(&self) -> bool
+    {
+        *self != Self::default()
+    }
+
+    // The format used in manifests, so that normalized files can be checked against the repository.
+    pub fn format(&self) -> String
+    {
+        format!("{} {}", self.sourceEncoding.getId(), self.lineEndings.getId())
+    }
+
+    pub fn parse(text: &str) -> Option<Self>
+    {
+        let (encodingId, lineEndingsId) = text.split_once(' ')?;
+        Some(Self{
+            sourceEncoding: SourceEncoding::fromId(encodingId)?,
+            lineEndings: LineEndings::fromId(lineEndingsId)?
+        })
+    }
+}
+
+// How a single file is converted, None fields mean the file is left as it is.
+#[derive(Clone, Copy)]
+pub struct FileTextFormat
+{
+    encoding: Option<&'static Encoding>,
+    lineEnding: Option<&'static str>
+}
+
+impl FileTextFormat
+{
+    const UNCHANGED: Self = Self{encoding: None, lineEnding: None};
+}
+
+// Converts text files to UTF-8 with configured line endings. Files marked with "-text" or "binary"
+// in .gitattributes of the reported commit are left intact, like binary ones, and "eol" attributes
+// take precedence over the settings.
+pub struct TextNormalizer<'a>
+{
+    settings: &'a TextNormalizationSettings,
+    repo: &'a Repository,
+    // read only when normalizing, as it needs the whole tree of the commit
+    attributes: Option<CommitAttributes>
+}
+
+impl<'a> TextNormalizer<'a>
+{
+    pub fn new(settings: &'a TextNormalizationSettings, repo: &'a Repository, commit: &git2::Commit) -> Self
+    {
+        let attributes = match settings.isEnabled() {
+            true => Some(repo.readAttributesOfCommit(commit)),
+            false => None
+        };
+        Self{settings, repo, attributes}
+    }
+
+    pub fn findFileTextFormat(&self, path: &Path, content: &[u8]) -> FileTextFormat
+    {
+        let attributes = match &self.attributes {
+            Some(attributes) => attributes,
+            None => return FileTextFormat::UNCHANGED
+        };
+        if isBinary(content) || attributes.isMarkedAsNotText(path) {
+            return FileTextFormat::UNCHANGED;
+        }
+        let lineEnding = match attributes.findEolAttribute(path).as_deref() {
+            Some(EOL_LF_VALUE) => Some(LF),
+            Some(EOL_CRLF_VALUE) => Some(CRLF),
+            _ => self.settings.lineEndings.getLineEnding()
+        };
+        let encoding = match (self.settings.sourceEncoding, std::str::from_utf8(content).is_ok()) {
+            (_, true) => Some(encoding_rs::UTF_8),
+            (SourceEncoding::Utf8, false) => None,
+            (sourceEncoding, false) => Some(sourceEncoding.findEncoding(content))
+        };
+        FileTextFormat{encoding, lineEnding}
+    }
+
+    // Deleted files are decoded by their old content, submodules have no content to be decoded
+    // and targets of symlinks are left as they are.
+    pub fn findDiffFileTextFormat(&self, delta: &git2::DiffDelta) -> FileTextFormat
+    {
+        let file = match delta.status() {
+            git2::Delta::Deleted => delta.old_file(),
+            _ => delta.new_file()
+        };
+        if !self.settings.isEnabled() || [git2::FileMode::Commit, git2::FileMode::Link].contains(&file.mode()) {
+            return FileTextFormat::UNCHANGED;
+        }
+        self.findFileTextFormat(file.path().unwrap(), self.repo.findBlob(file.id()).content())
+    }
+
+    // A byte order mark is kept as a part of the content, the same as when decoding lines of diffs,
+    // so that it cannot override the configured encoding.
+    pub fn normalizeFile<'b>(&self, path: &Path, content: &'b [u8]) -> Cow<'b, [u8]>
+    {
+        let format = self.findFileTextFormat(path, content);
+        match format.encoding {
+            Some(encoding) => {
+                let text = convertLineEndings(&encoding.decode_without_bom_handling(content).0, format.lineEnding);
+                Cow::Owned(text.into_bytes())
+            },
+            None => convertLineEndingsOfBytes(content, format.lineEnding)
+        }
+    }
+}
+
+// Decodes a line of a diff, which keeps its line ending in the content.
+pub fn normalizeLine(line: &[u8], format: &FileTextFormat) -> String
+{
+    match format.encoding {
+        Some(encoding) => convertLineEndings(&encoding.decode_without_bom_handling(line).0, format.lineEnding),
+        None => convertLineEndings(&String::from_utf8_lossy(line), format.lineEnding)
+    }
+}
+
+pub fn isBinary(content: &[u8]) -> bool
+{
+    content.iter().take(BINARY_CHECK_LENGTH).any(|byte| *byte == 0)
+}
+
+// Used only for files, which are not valid UTF-8.
+fn detectEncoding(content: &[u8]) -> &'static Encoding
+{
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(content, true);
+    detector.guess(None, true)
+}
+
+fn convertLineEndings(text: &str, lineEnding: Option<&str>) -> String
+{
+    match lineEnding {
+        Some(lineEnding) => text.replace(CRLF, LF).replace(LF, lineEnding),
+        None => text.into()
+    }
+}
+
+// Used for files, which cannot be decoded without losing their content.
+fn convertLineEndingsOfBytes<'a>(content: &'a [u8], lineEnding: Option<&str>) -> Cow<'a, [u8]>
+{
+    let lineEnding = match lineEnding {
+        Some(lineEnding) => lineEnding,
+        None => return Cow::Borrowed(content)
+    };
+
+    let mut converted = Vec::with_capacity(content.len());
+    let mut lines = content.split(|byte| *byte == b'\n').peekable();
+    while let Some(line) = lines.next() {
+        match lines.peek() {
+            Some(_) => {
+                converted.extend_from_slice(line.strip_suffix(b"\r").unwrap_or(line));
+                converted.extend_from_slice(lineEnding.as_bytes());
+            },
+            None => converted.extend_from_slice(line)
+        }
+    }
+    Cow::Owned(converted)
+}
@@ -1,31 +1,48 @@
-use crate::event::Event;
+use crate::event::{Event, Zoom};
 use crate::event_handling::{EventHandler, onUnknown, Sender};
 use crate::gui_element_provider::GuiElementProvider;
 use crate::line_number::LineNumber;
 use crate::source::Source;
+use crate::word_diff::CharRange;
 
 use gtk::gdk;
 use gtk::glib;
 use gtk::pango;
+use gtk::prelude::Cast as _;
 use gtk::prelude::CssProviderExt as _;
+use gtk::prelude::GtkMenuItemExt as _;
+use gtk::prelude::MenuShellExt as _;
+use gtk::prelude::GtkSettingsExt as _;
 use gtk::prelude::StyleContextExt as _;
 use gtk::prelude::TextBufferExt as _;
+use gtk::prelude::TextTagExt as _;
 use gtk::prelude::TextTagTableExt as _;
 use gtk::prelude::TextViewExt as _;
 use gtk::prelude::WidgetExt as _;
 use std::cmp::{min, max};
 
+const ALIGN_TO_LEFT: f64 = 0.0;
+const ALIGN_TO_TOP: f64 = 0.0;
+const INCLUDE_HIDDEN_CHARS: bool = true;
+// relative luminance of text above which the text is considered light
+const LIGHT_TEXT_LUMINANCE: f64 = 0.5;
+const NO_WITHIN_MARGIN: f64 = 0.0;
+const SEARCH_MATCH_MARGIN: f64 = 0.2;
 const NO_SEARCH_LIMIT: Option<&gtk::TextIter> = None;
 const SEARCH_VISIBLE_TEXT: gtk::TextSearchFlags = gtk::TextSearchFlags::from_bits_truncate(
     gtk::TextSearchFlags::VISIBLE_ONLY.bits() | gtk::TextSearchFlags::TEXT_ONLY.bits());
+const USE_ALIGNMENT: bool = true;
+const USE_NO_ALIGNMENT: bool = false;
 
 
 pub struct TextView
 {
+    widget: gtk::TextView,
     buffer: gtk::TextBuffer,
     sender: Sender,
     source: Source,
-    style: Style
+    style: Style,
+    searchTags: SearchTags
 }
 
 impl EventHandler for TextView
@@ -33,7 +50,7 @@ impl EventHandler for TextView
     fn handle(&mut self, source: Source, event: &Event)
     {
         match event {
-            Event::ZoomRequested(scrollEvent) => self.onZoomRequested(scrollEvent),
+            Event::ZoomRequested(zoom) => self.onZoomRequested(*zoom),
             _ => onUnknown(source, event)
         }
     }
@@ -44,22 +61,38 @@ impl TextView
     pub fn new(
         guiElementProvider: &GuiElementProvider,
         name: &str,
+        fontSize: Option<FontSize>,
         sender: Sender,
         source: Source)
         -> Self
     {
         let widget = guiElementProvider.get::<gtk::TextView>(name);
-        let newSelf = Self{
+        let mut newSelf = Self{
+            widget: widget.clone(),
             buffer: widget.buffer().unwrap(),
             sender,
             source,
             style: Style::new(&widget),
+            searchTags: SearchTags::new()
         };
 
+        newSelf.registerTags(&[&newSelf.searchTags.matched, &newSelf.searchTags.current]);
+        if let Some(fontSize) = fontSize {
+            newSelf.restoreFontSize(fontSize);
+        }
         newSelf.connectWidget(&widget);
         newSelf
     }
 
+    // None when the default font size of the theme is used.
+    pub fn getFontSize(&self) -> Option<FontSize>
+    {
+        match self.style.font.size == self.style.font.defaultSize {
+            true => None,
+            false => Some(self.style.font.size)
+        }
+    }
+
     pub fn setText(&self, text: &str)
     {
         self.buffer.set_text(text);
@@ -70,6 +103,11 @@ impl TextView
         self.setText("");
     }
 
+    pub fn getText(&self) -> String
+    {
+        self.buffer.text(&self.buffer.start_iter(), &self.buffer.end_iter(), INCLUDE_HIDDEN_CHARS).unwrap().into()
+    }
+
     pub fn registerTags(&self, tags: &[&gtk::TextTag])
     {
         let tagTable = self.buffer.tag_table().unwrap();
@@ -86,11 +124,70 @@ impl TextView
             &self.buffer.iter_at_line(endLine.into()));
     }
 
+    pub fn removeTag(&self, tag: &gtk::TextTag, startLine: LineNumber, endLine: LineNumber)
+    {
+        self.buffer.remove_tag(
+            tag,
+            &self.buffer.iter_at_line(startLine.into()),
+            &self.buffer.iter_at_line(endLine.into()));
+    }
+
+    pub fn scrollToLine(&self, line: LineNumber)
+    {
+        let mut iter = self.buffer.iter_at_line(line.into());
+        self.widget.scroll_to_iter(&mut iter, NO_WITHIN_MARGIN, USE_ALIGNMENT, ALIGN_TO_LEFT, ALIGN_TO_TOP);
+    }
+
+    // Not every dark theme sets the preference for a dark theme, but all of them use light text.
+    pub fn isThemeDark(&self) -> bool
+    {
+        let isDarkPreferred = gtk::Settings::default()
+            .is_some_and(|settings| settings.is_gtk_application_prefer_dark_theme());
+        let textColor = self.widget.style_context().color(gtk::StateFlags::NORMAL);
+        let luminance = 0.2126 * textColor.red() + 0.7152 * textColor.green() + 0.0722 * textColor.blue();
+        isDarkPreferred || luminance > LIGHT_TEXT_LUMINANCE
+    }
+
+    pub fn highlightSearchMatches(&self, matches: &[(LineNumber, CharRange)])
+    {
+        self.removeTagEverywhere(&self.searchTags.matched);
+        self.removeTagEverywhere(&self.searchTags.current);
+        // tags registered later have higher priority, search matches have to stand out over all of them
+        let tagCount = self.buffer.tag_table().unwrap().size();
+        self.searchTags.matched.set_priority(tagCount - 2);
+        self.searchTags.current.set_priority(tagCount - 1);
+        for (line, charRange) in matches {
+            self.applyTagInLine(&self.searchTags.matched, *line, charRange);
+        }
+    }
+
+    pub fn highlightCurrentSearchMatch(&self, line: LineNumber, charRange: &CharRange)
+    {
+        self.removeTagEverywhere(&self.searchTags.current);
+        self.applyTagInLine(&self.searchTags.current, line, charRange);
+        let mut iter = self.buffer.iter_at_line_offset(line.into(), charRange.start.try_into().unwrap());
+        self.widget.scroll_to_iter(&mut iter, SEARCH_MATCH_MARGIN, USE_NO_ALIGNMENT, ALIGN_TO_LEFT, ALIGN_TO_TOP);
+    }
+
+    pub fn clearCurrentSearchMatch(&self)
+    {
+        self.removeTagEverywhere(&self.searchTags.current);
+    }
+
     pub fn applyTagUntilEnd(&self, tag: &gtk::TextTag, startLine: LineNumber)
     {
         self.buffer.apply_tag(tag, &self.buffer.iter_at_line(startLine.into()), &self.buffer.end_iter());
     }
 
+    pub fn applyTagInLine(&self, tag: &gtk::TextTag, line: LineNumber, charRange: &CharRange)
+    {
+        let lineIndex = line.into();
+        self.buffer.apply_tag(
+            tag,
+            &self.buffer.iter_at_line_offset(lineIndex, charRange.start.try_into().unwrap()),
+            &self.buffer.iter_at_line_offset(lineIndex, charRange.end.try_into().unwrap()));
+    }
+
     pub fn applyTagUntilMatchEnd(&self, tag: &gtk::TextTag, startLine: LineNumber, pattern: &str)
     {
         let startIter = self.buffer.iter_at_line(startLine.into());
@@ -101,6 +198,11 @@ impl TextView
 
     // private
 
+    fn removeTagEverywhere(&self, tag: &gtk::TextTag)
+    {
+        self.buffer.remove_tag(tag, &self.buffer.start_iter(), &self.buffer.end_iter());
+    }
+
     fn connectWidget(&self, widget: &gtk::TextView)
     {
         let sender = self.sender.clone();
@@ -108,45 +210,66 @@ impl TextView
         widget.connect_scroll_event(move |_widget, event| {
             onScrolled(event, &sender, source)
         });
+
+        let sender = self.sender.clone();
+        widget.connect_key_press_event(move |_widget, event| {
+            onKeyPressed(event, &sender, source)
+        });
+
+        let sender = self.sender.clone();
+        widget.connect_populate_popup(move |_widget, popup| {
+            if let Some(menu) = popup.downcast_ref::<gtk::Menu>() {
+                addZoomMenuItems(menu, &sender, source);
+            }
+        });
     }
 
-    fn onZoomRequested(&mut self, event: &gdk::EventScroll)
+    fn onZoomRequested(&mut self, zoom: Zoom)
     {
-        let newFontSize = self.calculateNewFontSize(event);
+        let newFontSize = self.calculateNewFontSize(zoom);
         if self.style.font.size == newFontSize {
             return;
         }
 
-        self.loadCss(newFontSize, event);
+        self.loadCss(newFontSize, zoom);
     }
 
-    fn loadCss(&mut self, newFontSize: FontSize, event: &gdk::EventScroll)
+    fn loadCss(&mut self, newFontSize: FontSize, zoom: Zoom)
     {
         match self.style.cssProvider.load_from_data(self.formatCss(newFontSize).as_bytes()) {
             Ok(_) => self.style.font.size = newFontSize,
             Err(error) => {
                 validateCssError(&error);
                 self.style.font.maxSize = Some(self.style.font.size);
-                self.reloadCorrectCss(event);
+                self.reloadCorrectCss(zoom);
             }
         }
     }
 
-    fn reloadCorrectCss(&mut self, event: &gdk::EventScroll)
+    fn reloadCorrectCss(&mut self, zoom: Zoom)
     {
-        let newFontSize = self.calculateNewFontSize(event);
+        let newFontSize = self.calculateNewFontSize(zoom);
         match self.style.cssProvider.load_from_data(self.formatCss(newFontSize).as_bytes()) {
             Ok(_) => self.style.font.size = newFontSize,
             Err(e) => panic!("Unexpected error when reloading a corrected CSS: {}", e)
         }
     }
 
-    fn calculateNewFontSize(&self, event: &gdk::EventScroll) -> FontSize
+    // A stored size which cannot be used anymore is ignored, leaving the default one.
+    fn restoreFontSize(&mut self, fontSize: FontSize)
     {
-        match getY(event.delta()) {
-            y if y < 0.0 => self.calculateHigherFontSize(),
-            y if y > 0.0 => self.calculateLowerFontSize(),
-            _ => self.style.font.size
+        match self.style.cssProvider.load_from_data(self.formatCss(fontSize).as_bytes()) {
+            Ok(_) => self.style.font.size = fontSize,
+            Err(error) => validateCssError(&error)
+        }
+    }
+
+    fn calculateNewFontSize(&self, zoom: Zoom) -> FontSize
+    {
+        match zoom {
+            Zoom::In    => self.calculateHigherFontSize(),
+            Zoom::Out   => self.calculateLowerFontSize(),
+            Zoom::Reset => self.style.font.defaultSize
         }
     }
 
@@ -175,10 +298,69 @@ fn onScrolled(event: &gdk::EventScroll, sender: &Sender, source: Source) -> glib
         return glib::Propagation::Proceed;
     }
 
-    sender.send((source, Event::ZoomRequested(event.clone()))).unwrap();
+    match getY(event.delta()) {
+        y if y < 0.0 => sender.send((source, Event::ZoomRequested(Zoom::In))).unwrap(),
+        y if y > 0.0 => sender.send((source, Event::ZoomRequested(Zoom::Out))).unwrap(),
+        _ => ()
+    }
     glib::Propagation::Stop
 }
 
+fn onKeyPressed(event: &gdk::EventKey, sender: &Sender, source: Source) -> glib::Propagation
+{
+    if !event.state().contains(gdk::ModifierType::CONTROL_MASK) {
+        return glib::Propagation::Proceed;
+    }
+
+    use gdk::keys::constants as key;
+    let zoom = match event.keyval() {
+        key::plus | key::equal | key::KP_Add => Zoom::In,
+        key::minus | key::KP_Subtract        => Zoom::Out,
+        key::_0 | key::KP_0                  => Zoom::Reset,
+        _ => return glib::Propagation::Proceed
+    };
+    sender.send((source, Event::ZoomRequested(zoom))).unwrap();
+    glib::Propagation::Stop
+}
+
+fn addZoomMenuItems(menu: &gtk::Menu, sender: &Sender, source: Source)
+{
+    menu.append(&gtk::SeparatorMenuItem::new());
+    for (label, zoom) in [("Zoom in", Zoom::In), ("Zoom out", Zoom::Out), ("Reset zoom", Zoom::Reset)] {
+        let item = gtk::MenuItem::with_label(label);
+        let sender = sender.clone();
+        item.connect_activate(move |_item| sender.send((source, Event::ZoomRequested(zoom))).unwrap());
+        menu.append(&item);
+    }
+    menu.show_all();
+}
+
+struct SearchTags
+{
+    matched: gtk::TextTag,
+    current: gtk::TextTag
+}
+
+impl SearchTags
+{
+    fn new() -> Self
+    {
+        Self{
+            matched: makeSearchTag("searchMatch", "#fce94f"),
+            current: makeSearchTag("currentSearchMatch", "#f57900")
+        }
+    }
+}
+
+fn makeSearchTag(name: &str, backgroundColor: &str) -> gtk::TextTag
+{
+    let tag = gtk::TextTag::new(Some(name));
+    // the text has to stay readable also with dark themes
+    tag.set_foreground(Some("black"));
+    tag.set_background(Some(backgroundColor));
+    tag
+}
+
 struct Style
 {
     cssProvider: gtk::CssProvider,
@@ -203,11 +385,13 @@ impl Style
 struct Font
 {
     size: FontSize,
+    // size given by the theme
+    defaultSize: FontSize,
     maxSize: Option<FontSize>,
     family: FontFamily
 }
 
-type FontSize = i32;
+pub type FontSize = i32;
 type FontFamily = String;
 
 impl Font
@@ -216,8 +400,10 @@ impl Font
         where T: glib::IsA<gtk::Widget>
     {
         let fontDescription = getFontDescription(widget);
+        let size = getFontSize(&fontDescription);
         Self{
-            size: getFontSize(&fontDescription),
+            size,
+            defaultSize: size,
             maxSize: None,
             family: getFontFamily(&fontDescription)
         }
@@ -2,8 +2,9 @@ use crate::event::Event;
 use crate::event_handling::Sender;
 use crate::gui_element_provider::GuiElementProvider;
 use crate::source::Source;
-use crate::tree_view_column_config::{ColumnRenderer, ToggledAction, TreeViewColumnConfig};
+use crate::tree_view_column_config::{ColumnRenderer, EditedAction, ToggledAction, TreeViewColumnConfig};
 
+use gtk::prelude::CellRendererTextExt as _;
 use gtk::prelude::CellRendererToggleExt as _;
 use gtk::prelude::TreeSelectionExt as _;
 use gtk::prelude::TreeViewColumnExt as _;
@@ -46,11 +47,18 @@ impl TreeView
     fn setupColumns(&self, columnConfigs: Vec<TreeViewColumnConfig>)
     {
         for config in columnConfigs {
+            let index = config.index;
+            let sortColumnIndexOpt = config.sortColumnIndex;
             match config.renderer {
                 ColumnRenderer::Text => self.setupTextColumn(config),
+                ColumnRenderer::EditableText(editedAction) => self.setupEditableTextColumn(
+                    config.index, editedAction, config.isResizable),
                 ColumnRenderer::CheckButton(toggledAction) => self.setupCheckButtonColumn(
                     config.index, toggledAction, config.isResizable)
             }
+            if let Some(sortColumnIndex) = sortColumnIndexOpt {
+                self.widget.column(index).unwrap().set_sort_column_id(sortColumnIndex);
+            }
         }
     }
 
@@ -65,6 +73,18 @@ impl TreeView
         column.set_reorderable(true);
     }
 
+    fn setupEditableTextColumn(&self, index: i32, editedAction: EditedAction, isResizable: bool)
+    {
+        let renderer = gtk::CellRendererText::new();
+        renderer.set_editable(true);
+        renderer.connect_edited(move |renderer, treePath, text| { editedAction(renderer, treePath, text); });
+        let column = self.widget.column(index).unwrap();
+        column.pack_start(&renderer, EXPAND_IN_LAYOUT);
+        column.add_attribute(&renderer, "text", index);
+        column.set_resizable(isResizable);
+        column.set_reorderable(true);
+    }
+
     fn setupCheckButtonColumn(&self, index: i32, toggledAction: ToggledAction, isResizable: bool)
     {
         let renderer = gtk::CellRendererToggle::new();
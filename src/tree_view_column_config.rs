@@ -3,12 +3,16 @@ pub struct TreeViewColumnConfig
     pub index: i32,
     pub renderer: ColumnRenderer,
     pub isResizable: bool,
+    // index of the model column used for sorting when the column header is clicked
+    pub sortColumnIndex: Option<i32>
 }
 
 pub enum ColumnRenderer
 {
     Text,
+    EditableText(EditedAction),
     CheckButton(ToggledAction)
 }
 
+pub type EditedAction = Box<dyn Fn(&gtk::CellRendererText, gtk::TreePath, &str) + 'static>;
 pub type ToggledAction = Box<dyn Fn(&gtk::CellRendererToggle, gtk::TreePath) + 'static>;
@@ -0,0 +1,13 @@
+use crate::event::Event;
+use crate::event_handling::Sender;
+use crate::gui_element_provider::GuiElementProvider;
+use crate::source::Source;
+
+use gtk::prelude::ButtonExt as _;
+
+pub fn setupVerifyReportsButton(guiElementProvider: &GuiElementProvider, sender: Sender)
+{
+    let button = guiElementProvider.get::<gtk::Button>("verifyReportsButton");
+    button.connect_clicked(move |_widget|
+        sender.send((Source::VerifyReportsButton, Event::VerifyReportsRequested)).unwrap());
+}
@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+// comparing very long lines word by word would take too long and their highlighting would not be readable anyway
+const MAX_TOKEN_COMPARISONS: usize = 250_000;
+
+
+// ranges are in characters, as expected by gtk::TextIter offsets
+pub type CharRange = Range<usize>;
+
+#[derive(Default)]
+pub struct WordDiff
+{
+    pub removed: Vec<CharRange>,
+    pub added: Vec<CharRange>
+}
+
+// Finds words which differ between a removed line and the line added in its place.
+// Nothing is highlighted when the lines have no word in common, as then the whole lines differ.
+pub fn diffWords(oldLine: &str, newLine: &str) -> WordDiff
+{
+    let oldTokens = tokenize(oldLine);
+    let newTokens = tokenize(newLine);
+    if oldTokens.len() * newTokens.len() > MAX_TOKEN_COMPARISONS {
+        return WordDiff::default();
+    }
+
+    let (oldCommon, newCommon) = findCommonTokens(&oldTokens, &newTokens);
+    let haveCommonWord = oldTokens.iter().zip(&oldCommon).any(|(token, isCommon)| *isCommon && !token.isWhitespace);
+    if !haveCommonWord {
+        return WordDiff::default();
+    }
+    WordDiff{removed: collectChangedRanges(&oldTokens, &oldCommon), added: collectChangedRanges(&newTokens, &newCommon)}
+}
+
+struct Token<'a>
+{
+    text: &'a str,
+    charRange: CharRange,
+    isWhitespace: bool
+}
+
+#[derive(PartialEq)]
+enum CharClass
+{
+    Word,
+    Whitespace,
+    Other
+}
+
+fn classify(character: char) -> CharClass
+{
+    match character {
+        c if c.is_alphanumeric() || c == '_' => CharClass::Word,
+        c if c.is_whitespace() => CharClass::Whitespace,
+        _ => CharClass::Other
+    }
+}
+
+// Words and runs of whitespace are single tokens, every other character is a token on its own.
+fn tokenize(line: &str) -> Vec<Token<'_>>
+{
+    let mut tokens: Vec<Token> = vec![];
+    let mut tokenStart: Option<(usize, usize, CharClass)> = None;
+    let mut charCount = 0;
+    for (byteIndex, character) in line.char_indices() {
+        let charClass = classify(character);
+        if let Some((startByte, startChar, startClass)) = tokenStart.take() {
+            if startClass == charClass && charClass != CharClass::Other {
+                tokenStart = Some((startByte, startChar, startClass));
+            } else {
+                tokens.push(makeToken(line, startByte..byteIndex, startChar..charCount, startClass));
+            }
+        }
+        if tokenStart.is_none() {
+            tokenStart = Some((byteIndex, charCount, charClass));
+        }
+        charCount += 1;
+    }
+    if let Some((startByte, startChar, startClass)) = tokenStart {
+        tokens.push(makeToken(line, startByte..line.len(), startChar..charCount, startClass));
+    }
+    tokens
+}
+
+fn makeToken(line: &str, byteRange: Range<usize>, charRange: CharRange, charClass: CharClass) -> Token<'_>
+{
+    Token{text: &line[byteRange], charRange, isWhitespace: charClass == CharClass::Whitespace}
+}
+
+// longest common subsequence of tokens
+fn findCommonTokens(oldTokens: &[Token], newTokens: &[Token]) -> (Vec<bool>, Vec<bool>)
+{
+    let columns = newTokens.len() + 1;
+    let mut lengths = vec![0u32; (oldTokens.len() + 1) * columns];
+    for oldIndex in (0..oldTokens.len()).rev() {
+        for newIndex in (0..newTokens.len()).rev() {
+            lengths[oldIndex * columns + newIndex] = if oldTokens[oldIndex].text == newTokens[newIndex].text {
+                lengths[(oldIndex + 1) * columns + newIndex + 1] + 1
+            } else {
+                lengths[(oldIndex + 1) * columns + newIndex].max(lengths[oldIndex * columns + newIndex + 1])
+            };
+        }
+    }
+
+    let mut oldCommon = vec![false; oldTokens.len()];
+    let mut newCommon = vec![false; newTokens.len()];
+    let (mut oldIndex, mut newIndex) = (0, 0);
+    while oldIndex < oldTokens.len() && newIndex < newTokens.len() {
+        if oldTokens[oldIndex].text == newTokens[newIndex].text {
+            oldCommon[oldIndex] = true;
+            newCommon[newIndex] = true;
+            oldIndex += 1;
+            newIndex += 1;
+        } else if lengths[(oldIndex + 1) * columns + newIndex] >= lengths[oldIndex * columns + newIndex + 1] {
+            oldIndex += 1;
+        } else {
+            newIndex += 1;
+        }
+    }
+    (oldCommon, newCommon)
+}
+
+fn collectChangedRanges(tokens: &[Token], isCommon: &[bool]) -> Vec<CharRange>
+{
+    let mut ranges: Vec<CharRange> = vec![];
+    for (token, isCommon) in tokens.iter().zip(isCommon) {
+        if *isCommon {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(lastRange) if lastRange.end == token.charRange.start => lastRange.end = token.charRange.end,
+            _ => ranges.push(token.charRange.clone())
+        }
+    }
+    ranges
+}
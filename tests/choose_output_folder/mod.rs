@@ -1,3 +1,4 @@
 mod cancel_choosing_output_folder;
+#[allow(clippy::module_inception)]
 mod choose_output_folder;
 mod close_choosing_output_folder;
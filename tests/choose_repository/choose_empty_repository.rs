@@ -21,7 +21,7 @@ fn chooseEmptyRepository()
 
     clickChooseRepositoryFolderButton(&gui);
     let dialog = findFileChooserDialog();
-    setCurrentFolderInDialog(&repoDir, &dialog);
+    setCurrentFolderInDialog(repoDir, &dialog);
     acceptDialog(&dialog);
 
     assertRepositoryPathLabelTextIs(repoDirStr, &gui);
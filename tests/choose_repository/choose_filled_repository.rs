@@ -19,16 +19,16 @@ fn chooseFilledRepository()
     let repoDir = testResources.getRepoDir();
     let repoDirStr = repoDir.to_str().unwrap();
     let filePath = PathBuf::from("some_file");
-    makeNewStagedFile(&filePath, "some file content\n", &repoDir);
-    makeCommit(COMMIT_MESSAGE, &repoDir);
-    let commitDate = findLastCommitDateForLogView(&repoDir);
+    makeNewStagedFile(&filePath, "some file content\n", repoDir);
+    makeCommit(COMMIT_MESSAGE, repoDir);
+    let commitDate = findLastCommitDateForLogView(repoDir);
     let gui = makeGui(testResources.getConfigFilePath());
     assertRepositoryPathLabelTextIs("none", &gui);
     assertCommitLogViewIsEmpty(&gui);
 
     clickChooseRepositoryFolderButton(&gui);
     let dialog = findFileChooserDialog();
-    setCurrentFolderInDialog(&repoDir, &dialog);
+    setCurrentFolderInDialog(repoDir, &dialog);
     acceptDialog(&dialog);
 
     assertRepositoryPathLabelTextIs(repoDirStr, &gui);
@@ -0,0 +1,53 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_access::findCommitLogRow;
+use crate::common::gui_assertions::assertCommitDiffTextTaggedWith;
+use crate::common::gui_interactions::selectCommitLogRow;
+use crate::common::repository_setup::{changeStagedFile, makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{makeGui, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::PathBuf;
+
+
+rusty_fork_test! {
+#[test]
+fn highlightChangedWordsOfPairedLines()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    let filePath = PathBuf::from("main.rs");
+    makeNewStagedFile(&filePath, "let x = 1;\ncall(first, second);\n", repoDir);
+    makeCommit("Add file", repoDir);
+    changeStagedFile(&filePath, "let y = 1;\ncall(first.second);\n", repoDir);
+    makeCommit(CHANGE_COMMIT_MESSAGE, repoDir);
+    writeConfig("", &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    selectCommitLogRow(findCommitLogRow(CHANGE_COMMIT_MESSAGE, &gui), &gui);
+
+    assertCommitDiffTextTaggedWith("removedWord", &["x", ", "], &gui);
+    assertCommitDiffTextTaggedWith("addedWord", &["y", "."], &gui);
+}
+
+#[test]
+fn highlightNoWordsOfLinesWithoutCommonWords()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    let filePath = PathBuf::from("notes.txt");
+    makeNewStagedFile(&filePath, "first line\n", repoDir);
+    makeCommit("Add file", repoDir);
+    changeStagedFile(&filePath, "other text\n", repoDir);
+    makeCommit(CHANGE_COMMIT_MESSAGE, repoDir);
+    writeConfig("", &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    selectCommitLogRow(findCommitLogRow(CHANGE_COMMIT_MESSAGE, &gui), &gui);
+
+    assertCommitDiffTextTaggedWith("removedWord", &[], &gui);
+    assertCommitDiffTextTaggedWith("addedWord", &[], &gui);
+}
+}
+
+const CHANGE_COMMIT_MESSAGE: &str = "Change file";
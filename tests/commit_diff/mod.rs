@@ -0,0 +1 @@
+mod highlight_changed_words;
@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_assertions::assertCommitLogViewMessagesAre;
+use crate::common::repository_setup::{makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{COMMIT_AUTHOR, makeGui, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn showCommitsOfAuthor()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig(&format!("commitAuthorFilter = \"{}\"\n", COMMIT_AUTHOR), &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitLogViewMessagesAre(&[SOLO_COMMIT_MESSAGE, PAIR_COMMIT_MESSAGE], &gui);
+}
+
+#[test]
+fn showCommitsOfCoAuthor()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig(&format!("commitAuthorFilter = \"{}\"\n", CO_AUTHOR), &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitLogViewMessagesAre(&[PAIR_COMMIT_MESSAGE], &gui);
+}
+
+#[test]
+fn showNoCommitsOfOtherAuthor()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig("commitAuthorFilter = \"Bob Brown\"\n", &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitLogViewMessagesAre(&[], &gui);
+}
+}
+
+const SOLO_COMMIT_MESSAGE: &str = "Add feature";
+const PAIR_COMMIT_MESSAGE: &str = "Fix feature";
+const CO_AUTHOR: &str = "Jane Doe";
+
+// private
+
+fn makeCommits(repoDir: &Path)
+{
+    makeNewStagedFile(&PathBuf::from("some_file"), "some file content\n", repoDir);
+    makeCommit(SOLO_COMMIT_MESSAGE, repoDir);
+    makeNewStagedFile(&PathBuf::from("other_file"), "other file content\n", repoDir);
+    makeCommit(
+        &format!("{}\n\nCo-authored-by: {} <jane.doe@example.com>", PAIR_COMMIT_MESSAGE, CO_AUTHOR), repoDir);
+}
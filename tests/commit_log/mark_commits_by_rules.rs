@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_assertions::assertCommitsMarkedForReportAre;
+use crate::common::repository_setup::{makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{makeGui, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn markCommitsMatchingMessagePattern()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig("[[autoMarkingRules]]\nmessagePattern = \"^Add\"\n", &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitsMarkedForReportAre(&[FEATURE_COMMIT_MESSAGE], &gui);
+}
+
+#[test]
+fn markCommitsChangingPaths()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig("[[autoMarkingRules]]\npaths = [\"src\"]\n", &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitsMarkedForReportAre(&[FEATURE_COMMIT_MESSAGE], &gui);
+}
+
+#[test]
+fn markCommitsOfCoAuthor()
+{
+    let testResources = setupTest();
+    makeCommits(testResources.getRepoDir());
+    writeConfig(&format!("[[autoMarkingRules]]\nauthor = \"{}\"\n", CO_AUTHOR), &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitsMarkedForReportAre(&[DOCS_COMMIT_MESSAGE], &gui);
+}
+}
+
+const FEATURE_COMMIT_MESSAGE: &str = "Add feature";
+const DOCS_COMMIT_MESSAGE: &str = "Fix typo";
+const CO_AUTHOR: &str = "Jane Doe";
+
+// private
+
+fn makeCommits(repoDir: &Path)
+{
+    std::fs::create_dir(repoDir.join("src")).unwrap();
+    makeNewStagedFile(&PathBuf::from("src/main.rs"), "fn main() {}\n", repoDir);
+    makeCommit(FEATURE_COMMIT_MESSAGE, repoDir);
+    makeNewStagedFile(&PathBuf::from("README"), "Documentation\n", repoDir);
+    makeCommit(&format!("{}\n\nCo-authored-by: {} <jane.doe@example.com>", DOCS_COMMIT_MESSAGE, CO_AUTHOR), repoDir);
+}
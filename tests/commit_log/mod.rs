@@ -0,0 +1,2 @@
+mod filter_commits_by_author;
+mod mark_commits_by_rules;
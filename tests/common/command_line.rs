@@ -0,0 +1,13 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+
+pub fn runVerifyCommand(reportFolder: &Path, repoDir: &Path) -> Output
+{
+    Command::new(env!("CARGO_BIN_EXE_rusty-tax-break"))
+        .arg("verify")
+        .arg(reportFolder)
+        .arg(repoDir)
+        .output()
+        .unwrap()
+}
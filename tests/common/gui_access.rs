@@ -1,6 +1,11 @@
 use crate::common::test_gui::TestGui;
 
 use gtk::prelude::Cast as _;
+use gtk::prelude::TreeModelExt as _;
+use gtk::prelude::TreeViewExt as _;
+use gtk::prelude::WidgetExt as _;
+
+const COMMIT_LOG_MESSAGE_COLUMN: i32 = 1;
 
 
 pub fn findChooseRepositoryFolderButton(gui: &TestGui) -> gtk::Button
@@ -18,6 +23,27 @@ pub fn findCommitLogView(gui: &TestGui) -> gtk::TreeView
     gui.findWidget::<gtk::TreeView>("commitLogView")
 }
 
+// Commits made within the same second can be shown in any order, so their rows are looked up by messages.
+pub fn findCommitLogRow(message: &str, gui: &TestGui) -> i32
+{
+    let model = findCommitLogView(gui).model().unwrap();
+    let mut row = 0;
+    let mut iterOpt = model.iter_first();
+    while let Some(iter) = iterOpt {
+        if model.value(&iter, COMMIT_LOG_MESSAGE_COLUMN).get::<String>().unwrap() == message {
+            return row;
+        }
+        row += 1;
+        iterOpt = model.iter_next(&iter).then_some(iter);
+    }
+    panic!("No commit with message {:?} in the commit log view", message);
+}
+
+pub fn findCommitDiffView(gui: &TestGui) -> gtk::TextView
+{
+    gui.findWidget::<gtk::TextView>("commitDiffView")
+}
+
 pub fn findChooseOutputFolderButton(gui: &TestGui) -> gtk::Button
 {
     gui.findWidget::<gtk::Button>("chooseOutputFolderButton")
@@ -28,8 +54,21 @@ pub fn findOutputPathLabel(gui: &TestGui) -> gtk::Label
     gui.findWidget::<gtk::Label>("outputPathLabel")
 }
 
+pub fn findGenerateReportButton(gui: &TestGui) -> gtk::Button
+{
+    gui.findWidget::<gtk::Button>("generateReportButton")
+}
+
 pub fn findFileChooserDialog() -> gtk::FileChooserDialog
 {
     let mut topLevelWindows = gtk::Window::list_toplevels();
     topLevelWindows.remove(2).downcast::<gtk::FileChooserDialog>().unwrap()
 }
+
+pub fn findReportPreviewDialog() -> gtk::Dialog
+{
+    gtk::Window::list_toplevels().into_iter()
+        .filter(|widget| widget.is_visible())
+        .find_map(|widget| widget.downcast::<gtk::Dialog>().ok())
+        .unwrap()
+}
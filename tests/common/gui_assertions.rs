@@ -1,7 +1,10 @@
-use crate::common::gui_access::{findCommitLogView, findOutputPathLabel, findRepositoryPathLabel};
+use crate::common::gui_access::{findCommitDiffView, findCommitLogView, findOutputPathLabel, findRepositoryPathLabel};
 use crate::common::test_gui::TestGui;
 
 use gtk::prelude::LabelExt as _;
+use gtk::prelude::TextBufferExt as _;
+use gtk::prelude::TextTagTableExt as _;
+use gtk::prelude::TextViewExt as _;
 use gtk::prelude::TreeModelExt as _;
 use gtk::prelude::TreeViewExt as _;
 use std::path::Path;
@@ -26,17 +29,36 @@ pub fn assertRepositoryPathLabelTextIsPlaceholder(gui: &TestGui)
 
 pub fn assertCommitLogViewContentIs(expected: &[CommitLogRow], gui: &TestGui)
 {
-    let actual = collectCommitLogViewContent(&gui);
+    let actual = collectCommitLogViewContent(gui);
     assert_eq!(actual, expected, "\nActual commit log view content differs from expected");
 }
 
 pub fn assertCommitLogViewIsEmpty(gui: &TestGui)
 {
     let expected: Vec<CommitLogRow> = vec![];
-    let actual = collectCommitLogViewContent(&gui);
+    let actual = collectCommitLogViewContent(gui);
     assert_eq!(actual, expected, "\nActual commit log view content should be empty, but isn't");
 }
 
+// Commits made within the same second can be shown in any order, so messages are compared sorted.
+pub fn assertCommitLogViewMessagesAre(expectedMessages: &[&str], gui: &TestGui)
+{
+    let mut actual = collectCommitLogViewContent(gui).into_iter().map(|row| row.message).collect::<Vec<_>>();
+    actual.sort();
+    let mut expected = expectedMessages.to_vec();
+    expected.sort();
+    assert_eq!(actual, expected, "\nActual commit messages in the commit log view differ from expected");
+}
+
+pub fn assertCommitsMarkedForReportAre(expectedMessages: &[&str], gui: &TestGui)
+{
+    let actual = collectCommitLogViewContent(gui).into_iter()
+        .filter(|row| row.markedForReport)
+        .map(|row| row.message)
+        .collect::<Vec<_>>();
+    assert_eq!(actual, expectedMessages, "\nActual commits marked for report differ from expected");
+}
+
 pub fn makeCommitLogRow(markedForReport: bool, message: &str, date: &str, author: &str, email: &str) -> CommitLogRow
 {
     CommitLogRow {
@@ -58,6 +80,19 @@ pub struct CommitLogRow
     email: String
 }
 
+pub fn assertCommitDiffContains(expectedText: &str, gui: &TestGui)
+{
+    let buffer = findCommitDiffView(gui).buffer().unwrap();
+    let actual = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).unwrap();
+    assert!(actual.contains(expectedText), "\nCommit diff does not contain {:?}:\n{}", expectedText, actual);
+}
+
+pub fn assertCommitDiffTextTaggedWith(tagName: &str, expectedTexts: &[&str], gui: &TestGui)
+{
+    let actual = collectCommitDiffTextTaggedWith(tagName, gui);
+    assert_eq!(actual, expectedTexts, "\nActual commit diff text tagged with {} differs from expected", tagName);
+}
+
 pub fn assertOutputPathLabelTextIs(expectedText: &str, gui: &TestGui)
 {
     let label = findOutputPathLabel(gui);
@@ -66,7 +101,7 @@ pub fn assertOutputPathLabelTextIs(expectedText: &str, gui: &TestGui)
 
 pub fn assertOutputPathLabelTextIsPlaceholder(date: &Date, gui: &TestGui)
 {
-    assertOutputPathLabelTextIs(&makePlaceholderOutputPathLabelText(date), &gui);
+    assertOutputPathLabelTextIs(&makePlaceholderOutputPathLabelText(date), gui);
 }
 
 pub fn makeOutputPathLabelText(outputPathPrefix: &Path, date: &Date) -> String
@@ -93,6 +128,22 @@ fn collectCommitLogViewContent(gui: &TestGui) -> Vec<CommitLogRow>
     content
 }
 
+fn collectCommitDiffTextTaggedWith(tagName: &str, gui: &TestGui) -> Vec<String>
+{
+    let buffer = findCommitDiffView(gui).buffer().unwrap();
+    let tag = buffer.tag_table().unwrap().lookup(tagName).unwrap();
+    let mut texts = vec![];
+    let mut iter = buffer.start_iter();
+    while iter.forward_to_tag_toggle(Some(&tag)) {
+        if iter.starts_tag(Some(&tag)) {
+            let start = iter;
+            iter.forward_to_tag_toggle(Some(&tag));
+            texts.push(buffer.text(&start, &iter, false).unwrap().to_string());
+        }
+    }
+    texts
+}
+
 fn getMarkedForReportCell(model: &gtk::TreeModel, iter: &gtk::TreeIter) -> bool
 {
     getCellBool(model, iter, CommitLogColumn::MarkedForReport.into())
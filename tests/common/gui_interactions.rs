@@ -1,10 +1,17 @@
 use crate::common::event_processing::processEvents;
-use crate::common::gui_access::{findChooseOutputFolderButton, findChooseRepositoryFolderButton};
+use crate::common::gui_access::{
+    findChooseOutputFolderButton,
+    findChooseRepositoryFolderButton,
+    findCommitLogView,
+    findGenerateReportButton,
+    findReportPreviewDialog};
 use crate::common::test_gui::TestGui;
 
 use gtk::prelude::ButtonExt as _;
 use gtk::prelude::DialogExt as _;
 use gtk::prelude::FileChooserExt as _;
+use gtk::prelude::TreeSelectionExt as _;
+use gtk::prelude::TreeViewExt as _;
 use gtk::prelude::WidgetExt as _;
 use std::path::Path;
 
@@ -19,6 +26,23 @@ pub fn clickChooseOutputFolderButton(gui: &TestGui)
     clickButton(&findChooseOutputFolderButton(gui));
 }
 
+pub fn selectCommitLogRow(row: i32, gui: &TestGui)
+{
+    findCommitLogView(gui).selection().select_path(&gtk::TreePath::from_indicesv(&[row]));
+    processEvents();
+}
+
+// Confirms the preview of the report, so that the report gets written.
+pub fn generateReport(gui: &TestGui)
+{
+    clickButton(&findGenerateReportButton(gui));
+    let dialog = findReportPreviewDialog();
+    assert!(dialog.widget_for_response(gtk::ResponseType::Accept).unwrap().is_sensitive(),
+            "The report cannot be generated, as its preview has errors");
+    dialog.response(gtk::ResponseType::Accept);
+    processEvents();
+}
+
 pub fn setCurrentFolderInDialog(path: &Path, dialog: &gtk::FileChooserDialog)
 {
     // Changing the current folder in a file chooser dialog often doesn't seem to work on the first try.
@@ -1,9 +1,11 @@
+pub mod command_line;
 pub mod gui_access;
 pub mod gui_assertions;
 pub mod gui_interactions;
+pub mod report_files;
 pub mod repository_setup;
 pub mod test_gui;
+pub mod test_resources;
 pub mod test_setup;
 
 mod event_processing;
-mod test_resources;
@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+
+// Names of archives and folders of a report depend on commit ids, so files are looked up by their names.
+pub fn findReportFile(outputDir: &Path, fileName: &str) -> PathBuf
+{
+    findSingleFile(outputDir, fileName, |name| name == fileName)
+}
+
+pub fn findReportFileWithSuffix(outputDir: &Path, suffix: &str) -> PathBuf
+{
+    findSingleFile(outputDir, suffix, |name| name.ends_with(suffix))
+}
+
+// Contents of all files of reports by their paths relative to the output folder.
+pub fn readReportFiles(outputDir: &Path) -> BTreeMap<PathBuf, Vec<u8>>
+{
+    let mut paths = vec![];
+    collectFiles(outputDir, &|_name| true, &mut paths);
+    paths.into_iter()
+        .map(|path| (path.strip_prefix(outputDir).unwrap().to_owned(), std::fs::read(&path).unwrap()))
+        .collect()
+}
+
+pub fn removeReportFiles(outputDir: &Path)
+{
+    for entry in std::fs::read_dir(outputDir).unwrap() {
+        std::fs::remove_dir_all(entry.unwrap().path()).unwrap();
+    }
+}
+
+
+// private
+
+fn findSingleFile(outputDir: &Path, description: &str, matches: impl Fn(&str) -> bool) -> PathBuf
+{
+    let mut found = vec![];
+    collectFiles(outputDir, &matches, &mut found);
+    match found.len() {
+        1 => found.remove(0),
+        count => panic!("Expected one file {:?} in the report, found {}: {:?}", description, count, found)
+    }
+}
+
+fn collectFiles(dir: &Path, matches: &impl Fn(&str) -> bool, found: &mut Vec<PathBuf>)
+{
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collectFiles(&path, matches, found);
+        } else if matches(&path.file_name().unwrap().to_string_lossy()) {
+            found.push(path);
+        }
+    }
+}
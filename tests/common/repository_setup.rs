@@ -15,17 +15,29 @@ const DATE_TIME_FORMAT: &[FormatItem] =
 
 
 pub fn makeNewStagedFile(filePath: &Path, content: &str, repositoryDir: &Path)
+{
+    makeNewStagedFileWithBytes(filePath, content.as_bytes(), repositoryDir);
+}
+
+// for content in other encodings than UTF-8
+pub fn makeNewStagedFileWithBytes(filePath: &Path, content: &[u8], repositoryDir: &Path)
 {
     makeNewUnstagedFile(filePath, content, repositoryDir);
     stageFile(filePath, repositoryDir);
 }
 
+pub fn changeStagedFile(filePath: &Path, content: &str, repositoryDir: &Path)
+{
+    std::fs::write(repositoryDir.join(filePath), content).unwrap();
+    stageFile(filePath, repositoryDir);
+}
+
 pub fn makeCommit(message: &str, repositoryDir: &Path)
 {
-    let status = Command::new("git").args(&["commit", "-m", message])
-        .current_dir(&repositoryDir).stdout(Stdio::null()).status().unwrap();
-    assert_eq!(true, status.success(),
-               "Failed to create a commit with message \"{}\", command finished with {}", message, status);
+    let status = Command::new("git").args(["commit", "-m", message])
+        .current_dir(repositoryDir).stdout(Stdio::null()).status().unwrap();
+    assert!(status.success(),
+            "Failed to create a commit with message \"{}\", command finished with {}", message, status);
 }
 
 pub fn findLastCommitDateForLogView(repoDir: &Path) -> String
@@ -33,13 +45,18 @@ pub fn findLastCommitDateForLogView(repoDir: &Path) -> String
     findLastCommitDate(repoDir).format(DATE_TIME_FORMAT).unwrap()
 }
 
+pub fn findLastCommitDateForReport(repoDir: &Path) -> String
+{
+    findLastCommitDate(repoDir).format(&Rfc2822).unwrap()
+}
+
 
 // private
 
-fn makeNewUnstagedFile(filePath: &Path, content: &str, repositoryDir: &Path)
+fn makeNewUnstagedFile(filePath: &Path, content: &[u8], repositoryDir: &Path)
 {
     let mut file = makeNewWritableFile(&repositoryDir.join(filePath));
-    file.write(content.as_bytes()).unwrap();
+    file.write_all(content).unwrap();
 }
 
 fn makeNewWritableFile(filePath: &Path) -> File
@@ -49,10 +66,10 @@ fn makeNewWritableFile(filePath: &Path) -> File
 
 fn stageFile(filePath: &Path, repositoryDir: &Path)
 {
-    let status = Command::new("git").args(&["add", filePath.to_str().unwrap()])
-        .current_dir(&repositoryDir).status().unwrap();
-    assert_eq!(true, status.success(),
-               "Failed to stage file \"{:?}\", command finished with {}", filePath, status);
+    let status = Command::new("git").args(["add", filePath.to_str().unwrap()])
+        .current_dir(repositoryDir).status().unwrap();
+    assert!(status.success(),
+            "Failed to stage file \"{:?}\", command finished with {}", filePath, status);
 }
 
 fn findLastCommitDate(repoDir: &Path) -> OffsetDateTime
@@ -71,6 +88,6 @@ fn getCommandStdoutString(commandParts: &[&str], repositoryDir: &Path) -> String
 fn getCommandResults(commandParts: &[&str], repositoryDir: &Path) -> std::process::Output
 {
     let mut command = Command::new(commandParts[0]);
-    command.args(&commandParts[1..]).current_dir(&repositoryDir);
+    command.args(&commandParts[1..]).current_dir(repositoryDir);
     command.output().unwrap()
 }
@@ -41,6 +41,13 @@ pub fn makeTemporaryDir() -> (TempDir, PathBuf)
     (tempDir, path)
 }
 
+// Settings are given like in the config file, the repository of the test is set before them.
+pub fn writeConfig(settings: &str, testResources: &TestResources)
+{
+    let content = format!("repository = {:?}\n{}", testResources.getRepoDir(), settings);
+    std::fs::write(testResources.getConfigFilePath(), content).unwrap();
+}
+
 pub fn makeGui(configPath: &Path) -> TestGui
 {
     let gui = Gui::new(&ConfigPath::new(configPath));
@@ -71,11 +78,11 @@ fn initializeGitRepository(repoDir: &Path)
 fn initializeGitRepositoryWith(commandParts: &[&str], repoDir: &Path)
 {
     let mut command = Command::new(commandParts[0]);
-    command.args(&commandParts[1..]).current_dir(&repoDir).stdout(Stdio::null());
+    command.args(&commandParts[1..]).current_dir(repoDir).stdout(Stdio::null());
     let status = command.status().unwrap();
-    assert_eq!(true, status.success(),
-               "Failed to initialize git repository.\nPath: {:?}\nCommand: {:?}\nCommand status: {}",
-               repoDir, command, status);
+    assert!(status.success(),
+            "Failed to initialize git repository.\nPath: {:?}\nCommand: {:?}\nCommand status: {}",
+            repoDir, command, status);
 }
 
 fn getAppWindow() -> gtk::ApplicationWindow
@@ -0,0 +1 @@
+mod relative_dates;
@@ -0,0 +1,69 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_assertions::{assertCommitDiffContains, assertCommitLogViewContentIs, makeCommitLogRow};
+use crate::common::gui_interactions::{generateReport, selectCommitLogRow};
+use crate::common::report_files::findReportFile;
+use crate::common::repository_setup::{findLastCommitDateForReport, makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{COMMIT_AUTHOR, COMMIT_EMAIL, makeGui, makeTemporaryDir, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::PathBuf;
+
+
+rusty_fork_test! {
+#[test]
+fn showRelativeDatesInCommitLog()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    makeNewStagedFile(&PathBuf::from("some_file"), "some file content\n", repoDir);
+    makeCommit(COMMIT_MESSAGE, repoDir);
+    writeConfig("dateFormat = \"Relative\"\n", &testResources);
+
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    assertCommitLogViewContentIs(
+        &[makeCommitLogRow(NOT_MARKED_FOR_REPORT, COMMIT_MESSAGE, "just now", COMMIT_AUTHOR, COMMIT_EMAIL)], &gui);
+}
+
+#[test]
+fn showRelativeDatesInCommitDiff()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    makeNewStagedFile(&PathBuf::from("some_file"), "some file content\n", repoDir);
+    makeCommit(COMMIT_MESSAGE, repoDir);
+    writeConfig("dateFormat = \"Relative\"\n", &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    selectCommitLogRow(0, &gui);
+
+    assertCommitDiffContains("AuthorDate: just now\n", &gui);
+}
+
+#[test]
+fn writeAbsoluteDatesIntoReportsInsteadOfRelativeOnes()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    makeNewStagedFile(&PathBuf::from("some_file"), "some file content\n", repoDir);
+    makeCommit(COMMIT_MESSAGE, repoDir);
+    let commitDate = findLastCommitDateForReport(repoDir);
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    writeConfig(
+        &format!("dateFormat = \"Relative\"\noutputPathPrefix = {:?}\n\
+                  [[autoMarkingRules]]\nauthor = \"{}\"\n\
+                  [reportSettings]\narchiveFormat = \"Directory\"\narchiveComposition = \"DiffOnly\"\n",
+                 outputDir, COMMIT_AUTHOR),
+        &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+
+    generateReport(&gui);
+
+    let diff = std::fs::read_to_string(findReportFile(&outputDir, "changes.diff")).unwrap();
+    assert!(diff.contains(&format!("AuthorDate: {}\n", commitDate)), "\nDiff has no absolute date:\n{}", diff);
+}
+}
+
+const NOT_MARKED_FOR_REPORT: bool = false;
+const COMMIT_MESSAGE: &str = "initial commit";
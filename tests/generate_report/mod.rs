@@ -0,0 +1,2 @@
+mod normalize_report_text;
+mod regenerate_identical_reports;
@@ -0,0 +1,71 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_interactions::generateReport;
+use crate::common::report_files::findReportFile;
+use crate::common::repository_setup::{makeCommit, makeNewStagedFileWithBytes};
+use crate::common::test_resources::TestResources;
+use crate::common::test_setup::{COMMIT_AUTHOR, makeGui, makeTemporaryDir, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn convertWindows1250FilesToUtf8WithLf()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    makeNewStagedFileWithBytes(&PathBuf::from("legacy.txt"), &encodeWindows1250(CRLF_TEXT), repoDir);
+    makeCommit("Add legacy file", repoDir);
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+
+    generateNormalizedReport(&outputDir, &testResources);
+
+    assert_eq!(LF_TEXT, std::fs::read_to_string(findReportFile(&outputDir, "legacy.txt")).unwrap());
+    let diff = std::fs::read_to_string(findReportFile(&outputDir, "changes.diff")).unwrap();
+    assert!(diff.contains("+Zażółć gęślą jaźń\n+Pchnąć w tę łódź jeża\n"), "\nDiff is not normalized:\n{}", diff);
+}
+
+#[test]
+fn followAttributesOfReportedCommit()
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    let content = encodeWindows1250(CRLF_TEXT);
+    makeNewStagedFileWithBytes(&PathBuf::from(".gitattributes"), ATTRIBUTES.as_bytes(), repoDir);
+    makeNewStagedFileWithBytes(&PathBuf::from("intact.txt"), &content, repoDir);
+    makeNewStagedFileWithBytes(&PathBuf::from("windows.txt"), &content, repoDir);
+    makeCommit("Add files with attributes", repoDir);
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+
+    generateNormalizedReport(&outputDir, &testResources);
+
+    assert_eq!(content, std::fs::read(findReportFile(&outputDir, "intact.txt")).unwrap());
+    assert_eq!(CRLF_TEXT, std::fs::read_to_string(findReportFile(&outputDir, "windows.txt")).unwrap());
+}
+}
+
+const CRLF_TEXT: &str = "Zażółć gęślą jaźń\r\nPchnąć w tę łódź jeża\r\n";
+const LF_TEXT: &str = "Zażółć gęślą jaźń\nPchnąć w tę łódź jeża\n";
+const ATTRIBUTES: &str = "intact.txt -text\nwindows.txt eol=crlf\n";
+
+// private
+
+// Writes a folder report with one copy of each file, so that files can be found by their names.
+fn generateNormalizedReport(outputDir: &Path, testResources: &TestResources)
+{
+    writeConfig(
+        &format!("outputPathPrefix = {:?}\n[[autoMarkingRules]]\nauthor = \"{}\"\n\
+                  [reportSettings]\narchiveFormat = \"Directory\"\narchiveComposition = \"Combined\"\n\
+                  [textNormalization.{:?}]\nsourceEncoding = \"Windows1250\"\nlineEndings = \"Lf\"\n",
+                 outputDir, COMMIT_AUTHOR, testResources.getRepoDir()),
+        testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+    generateReport(&gui);
+}
+
+fn encodeWindows1250(text: &str) -> Vec<u8>
+{
+    encoding_rs::WINDOWS_1250.encode(text).0.into_owned()
+}
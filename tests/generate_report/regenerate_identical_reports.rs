@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+
+use crate::common::gui_interactions::generateReport;
+use crate::common::report_files::{readReportFiles, removeReportFiles};
+use crate::common::repository_setup::{makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{COMMIT_AUTHOR, makeGui, makeTemporaryDir, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn regenerateIdenticalZipArchives()
+{
+    assertRegeneratedReportIsIdentical("Zip");
+}
+
+#[test]
+fn regenerateIdenticalTarGzArchives()
+{
+    assertRegeneratedReportIsIdentical("TarGz");
+}
+
+#[test]
+fn regenerateIdenticalTarZstArchives()
+{
+    assertRegeneratedReportIsIdentical("TarZst");
+}
+}
+
+// private
+
+fn assertRegeneratedReportIsIdentical(archiveFormat: &str)
+{
+    let testResources = setupTest();
+    makeCommitWithSeveralFiles(testResources.getRepoDir());
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    writeConfig(
+        &format!("outputPathPrefix = {:?}\n[[autoMarkingRules]]\nauthor = \"{}\"\n\
+                  [reportSettings]\narchiveFormat = \"{}\"\n",
+                 outputDir, COMMIT_AUTHOR, archiveFormat),
+        &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+    generateReport(&gui);
+    let firstReport = readReportFiles(&outputDir);
+    assert!(!firstReport.is_empty());
+    removeReportFiles(&outputDir);
+
+    generateReport(&gui);
+
+    assert_eq!(firstReport, readReportFiles(&outputDir), "\nRegenerated report differs from the first one");
+}
+
+fn makeCommitWithSeveralFiles(repoDir: &Path)
+{
+    std::fs::create_dir(repoDir.join("src")).unwrap();
+    makeNewStagedFile(&PathBuf::from("src/main.rs"), "fn main() {}\n", repoDir);
+    makeNewStagedFile(&PathBuf::from("README"), "Documentation\n", repoDir);
+    makeNewStagedFile(&PathBuf::from("run.sh"), "#!/bin/sh\n", repoDir);
+    makeCommit("Add files", repoDir);
+}
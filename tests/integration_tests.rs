@@ -2,4 +2,9 @@
 
 mod choose_output_folder;
 mod choose_repository;
+mod commit_diff;
+mod commit_log;
 mod common;
+mod date_format;
+mod generate_report;
+mod verify_reports;
@@ -0,0 +1,2 @@
+mod verify_generated_reports;
+mod verify_modified_reports;
@@ -0,0 +1,64 @@
+#![allow(non_snake_case)]
+
+use crate::common::command_line::runVerifyCommand;
+use crate::common::gui_interactions::generateReport;
+use crate::common::repository_setup::{makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{COMMIT_AUTHOR, makeGui, makeTemporaryDir, setupTest, writeConfig};
+
+use rusty_fork::rusty_fork_test;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn verifyZipReportWithSpecialFileNames()
+{
+    assertGeneratedReportIsVerified("Zip");
+}
+
+#[test]
+fn verifyTarGzReportWithSpecialFileNames()
+{
+    assertGeneratedReportIsVerified("TarGz");
+}
+
+#[test]
+fn verifyFolderReportWithSpecialFileNames()
+{
+    assertGeneratedReportIsVerified("Directory");
+}
+}
+
+// manifests escape line breaks and backslashes in paths, like sha256sum does
+const SPECIAL_FILE_NAMES: [&str; 3] = ["file with spaces.txt", "line\nbreak.txt", "back\\slash.txt"];
+
+// private
+
+fn assertGeneratedReportIsVerified(archiveFormat: &str)
+{
+    let testResources = setupTest();
+    let repoDir = testResources.getRepoDir();
+    makeCommitWithSpecialFileNames(repoDir);
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    writeConfig(
+        &format!("outputPathPrefix = {:?}\n[[autoMarkingRules]]\nauthor = \"{}\"\n\
+                  [reportSettings]\narchiveFormat = \"{}\"\n",
+                 outputDir, COMMIT_AUTHOR, archiveFormat),
+        &testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+    generateReport(&gui);
+
+    let output = runVerifyCommand(&outputDir, repoDir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "\nVerification failed:\n{}", stdout);
+    assert!(stdout.contains("Checked archives: 2, problems found: 0, warnings: 0"), "\nUnexpected totals:\n{}", stdout);
+}
+
+fn makeCommitWithSpecialFileNames(repoDir: &Path)
+{
+    for fileName in SPECIAL_FILE_NAMES {
+        makeNewStagedFile(&PathBuf::from(fileName), "some file content\n", repoDir);
+    }
+    makeCommit("Add files with special names", repoDir);
+}
@@ -0,0 +1,95 @@
+#![allow(non_snake_case)]
+
+use crate::common::command_line::runVerifyCommand;
+use crate::common::gui_interactions::generateReport;
+use crate::common::report_files::{findReportFile, findReportFileWithSuffix};
+use crate::common::repository_setup::{makeCommit, makeNewStagedFile};
+use crate::common::test_setup::{COMMIT_AUTHOR, makeGui, makeTemporaryDir, setupTest, writeConfig};
+use crate::common::test_resources::TestResources;
+
+use rusty_fork::rusty_fork_test;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+
+rusty_fork_test! {
+#[test]
+fn findModifiedFileInReportFolder()
+{
+    let testResources = setupTest();
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    generateCombinedReport("Directory", &outputDir, &testResources);
+
+    std::fs::write(findReportFile(&outputDir, REPOSITORY_FILE_NAME), "modified content\n").unwrap();
+    let output = runVerifyCommand(&outputDir, testResources.getRepoDir());
+
+    assertVerificationFoundProblem(&format!("full_files/{} has been modified", REPOSITORY_FILE_NAME), &output);
+}
+
+#[test]
+fn findExtraFileInReportFolder()
+{
+    let testResources = setupTest();
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    generateCombinedReport("Directory", &outputDir, &testResources);
+
+    let reportFolder = findReportFile(&outputDir, "changes.diff").parent().unwrap().to_owned();
+    std::fs::write(reportFolder.join("extra.txt"), "extra content\n").unwrap();
+    let output = runVerifyCommand(&outputDir, testResources.getRepoDir());
+
+    assertVerificationFoundProblem("extra.txt is an extra file, which is not in the manifest", &output);
+}
+
+#[test]
+fn findModifiedZipArchive()
+{
+    let testResources = setupTest();
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    generateCombinedReport("Zip", &outputDir, &testResources);
+
+    let archivePath = findReportFileWithSuffix(&outputDir, ".zip");
+    let mut archive = std::fs::OpenOptions::new().append(true).open(archivePath).unwrap();
+    archive.write_all(b"appended bytes").unwrap();
+    let output = runVerifyCommand(&outputDir, testResources.getRepoDir());
+
+    assertVerificationFoundProblem("the archive has been modified", &output);
+}
+
+#[test]
+fn findMissingArchive()
+{
+    let testResources = setupTest();
+    let (_outputDirGuard, outputDir) = makeTemporaryDir();
+    generateCombinedReport("Zip", &outputDir, &testResources);
+
+    std::fs::remove_file(findReportFileWithSuffix(&outputDir, ".zip")).unwrap();
+    let output = runVerifyCommand(&outputDir, testResources.getRepoDir());
+
+    assertVerificationFoundProblem("the archive is missing", &output);
+}
+}
+
+const REPOSITORY_FILE_NAME: &str = "some_file";
+
+// private
+
+fn generateCombinedReport(archiveFormat: &str, outputDir: &Path, testResources: &TestResources)
+{
+    let repoDir = testResources.getRepoDir();
+    makeNewStagedFile(&PathBuf::from(REPOSITORY_FILE_NAME), "some file content\n", repoDir);
+    makeCommit("Add file", repoDir);
+    writeConfig(
+        &format!("outputPathPrefix = {:?}\n[[autoMarkingRules]]\nauthor = \"{}\"\n\
+                  [reportSettings]\narchiveFormat = \"{}\"\narchiveComposition = \"Combined\"\n",
+                 outputDir, COMMIT_AUTHOR, archiveFormat),
+        testResources);
+    let gui = makeGui(testResources.getConfigFilePath());
+    generateReport(&gui);
+}
+
+fn assertVerificationFoundProblem(expectedProblem: &str, output: &std::process::Output)
+{
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "\nVerification should fail, but succeeded:\n{}", stdout);
+    assert!(stdout.contains(expectedProblem), "\nVerification did not find {:?}:\n{}", expectedProblem, stdout);
+}